@@ -0,0 +1,129 @@
+use crate::event::Event;
+use crate::map::{Map, Tile};
+use crate::robot::{Robot, RobotType, Strategy, Task};
+use std::cmp::Reverse;
+
+// How miners and scientists decide which resource to go for
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Allocation {
+    // Every robot heads for the nearest resource, even if others are already on their way
+    #[default]
+    Nearest,
+    // The station auctions every resource off to exactly one robot
+    Auction,
+}
+
+impl std::str::FromStr for Allocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nearest" => Ok(Allocation::Nearest),
+            "auction" => Ok(Allocation::Auction),
+            _ => Err(format!("unknown allocation '{}' (expected nearest or auction)", s)),
+        }
+    }
+}
+
+// One robot's offer to harvest the resource at (x, y)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Bid {
+    distance: usize,
+    // Among equally close robots the one with more energy to spare wins
+    energy: Reverse<usize>,
+    robot: usize,
+    x: usize,
+    y: usize,
+}
+
+// Runs a sequential auction each tick: the cheapest bid across all robots and resources
+// wins, both leave the auction, and so on until no bids are left
+pub struct Auctioneer {
+    pub allocation: Allocation,
+    // Times a robot was handed a different resource than before
+    pub assignments: usize,
+}
+
+impl Auctioneer {
+    pub fn new(allocation: Allocation) -> Self {
+        Self { allocation, assignments: 0 }
+    }
+
+    pub fn tick(&mut self, map: &Map, robots: &mut [Robot]) -> Vec<Event> {
+        let mut events = Vec::new();
+        if self.allocation != Allocation::Auction {
+            return events;
+        }
+
+        let mut resources = Vec::new();
+        for (y, row) in map.grid.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                if tile == Tile::Mineral || tile == Tile::Scientific {
+                    resources.push((x, y, tile));
+                }
+            }
+        }
+
+        // Robots only bid on resources they can reach with the energy they have left
+        let mut bids = Vec::new();
+        for robot in robots.iter_mut() {
+            let Some(wanted) = bidding_for(robot) else {
+                robot.task = Task::Unmanaged;
+                continue;
+            };
+            bids.extend(
+                resources
+                    .iter()
+                    .filter(|&&(_, _, tile)| tile == wanted)
+                    .map(|&(x, y, _)| Bid {
+                        distance: robot.x.abs_diff(x) + robot.y.abs_diff(y),
+                        energy: Reverse(robot.energy),
+                        robot: robot.id,
+                        x,
+                        y,
+                    })
+                    .filter(|bid| bid.distance <= robot.energy),
+            );
+        }
+        bids.sort_unstable();
+
+        // Rival factions hold separate auctions for the same resources
+        let mut awarded: Vec<Option<(usize, usize)>> = vec![None; robots.len()];
+        let mut taken = Vec::new();
+        for bid in bids {
+            let faction = robots[bid.robot].faction;
+            if awarded[bid.robot].is_none() && !taken.contains(&(faction, bid.x, bid.y)) {
+                awarded[bid.robot] = Some((bid.x, bid.y));
+                taken.push((faction, bid.x, bid.y));
+            }
+        }
+
+        for robot in robots.iter_mut().filter(|r| bidding_for(r).is_some()) {
+            let task = match awarded[robot.id] {
+                Some((x, y)) => Task::Assigned(x, y),
+                None => Task::Idle,
+            };
+            if task != robot.task {
+                if let Task::Assigned(x, y) = task {
+                    self.assignments += 1;
+                    events.push(Event::TaskAssigned { robot: robot.id, x, y });
+                }
+            }
+            robot.task = task;
+        }
+
+        events
+    }
+}
+
+// The resource a robot would head for on its own, if it takes part in auctions at all
+fn bidding_for(robot: &Robot) -> Option<Tile> {
+    if robot.strategy != Strategy::Standard || robot.returning || robot.is_broken() || robot.energy <= 5 {
+        return None;
+    }
+    match robot.robot_type {
+        RobotType::Miner => Some(Tile::Mineral),
+        RobotType::Scientist => Some(Tile::Scientific),
+        RobotType::Explorer => None,
+    }
+}
@@ -0,0 +1,443 @@
+use robot_swarm::auction::Allocation;
+use robot_swarm::cooperation::Cooperation;
+use robot_swarm::export::ExportOptions;
+use robot_swarm::frontier::Exploration;
+use robot_swarm::mission::Mission;
+use robot_swarm::pheromone::Stigmergy;
+use robot_swarm::protocol::Episode;
+use robot_swarm::radio::Radio;
+use robot_swarm::reliability::Reliability;
+use robot_swarm::research::Research;
+use robot_swarm::render::RenderMode;
+use robot_swarm::robot::{RobotType, Strategy};
+use robot_swarm::script::parse_robot_type;
+use robot_swarm::simulation::{Faction, Swarm, MAX_FACTIONS};
+use robot_swarm::station::{Expansion, Inventory};
+use robot_swarm::sweep::SweepGrid;
+use robot_swarm::watchdog::Limits;
+use robot_swarm::world::Dynamics;
+use std::time::Duration;
+
+pub enum Command {
+    Run(Box<RunOptions>),
+    Replay { path: String, render: RenderMode },
+    Sweep(SweepOptions),
+    Control(ControlOptions),
+}
+
+pub struct RunOptions {
+    pub width: usize,
+    pub height: usize,
+    pub log: Option<String>,
+    pub report: Option<String>,
+    pub tick_report: Option<String>,
+    pub visits_csv: Option<String>,
+    pub energy_csv: Option<String>,
+    pub mission: Option<Mission>,
+    pub strategy: Strategy,
+    pub rivals: Vec<Faction>,
+    pub stations: Vec<(usize, usize)>,
+    pub expansion: Option<Expansion>,
+    pub dynamics: Dynamics,
+    pub reliability: Reliability,
+    pub radio: Radio,
+    pub stigmergy: Stigmergy,
+    pub exploration: Exploration,
+    pub allocation: Allocation,
+    pub cooperation: Cooperation,
+    pub research: Research,
+    // Script files and the robot type each one controls, `None` for every type
+    pub scripts: Vec<(Option<RobotType>, String)>,
+    // Address of the live observation server, needs the `server` feature
+    pub serve: Option<String>,
+    pub limits: Limits,
+    pub stuck_ticks: u64,
+    pub tick_ms: u64,
+    // No sleeping between ticks, drawing every `render_every` ticks
+    pub fast: bool,
+    pub render_every: u64,
+    // No rendering and no sleeping, for benchmarking
+    pub headless: bool,
+    pub render: RenderMode,
+    pub export: ExportOptions,
+}
+
+pub struct SweepOptions {
+    pub grid: SweepGrid,
+    pub threads: usize,
+    pub output: Option<String>,
+}
+
+pub struct ControlOptions {
+    pub episode: Episode,
+    // Serve clients on this local address instead of stdin/stdout
+    pub listen: Option<String>,
+}
+
+pub fn parse(args: &[String]) -> Command {
+    match args.get(1).map(String::as_str) {
+        Some("replay") => return parse_replay(&args[2..]),
+        Some("sweep") => return Command::Sweep(parse_sweep(&args[2..])),
+        Some("control") => return Command::Control(parse_control(&args[2..])),
+        _ => {}
+    }
+
+    let default_width = 20;
+    let default_height = 10;
+
+    let mut positional = Vec::new();
+    let mut log = None;
+    let mut report = None;
+    let mut tick_report = None;
+    let mut visits_csv = None;
+    let mut energy_csv = None;
+    let mut mission = None;
+    let mut factions = 1;
+    let mut strategies = Vec::new();
+    let mut stations = Vec::new();
+    let mut outpost_cost = None;
+    let mut outpost_spacing = 8;
+    let mut inventory = Inventory::Shared;
+    let mut dynamics = Dynamics::default();
+    let mut reliability = Reliability::default();
+    let mut radio = Radio::default();
+    let mut stigmergy = Stigmergy::default();
+    let mut exploration = Exploration::default();
+    let mut allocation = Allocation::default();
+    let mut cooperation = Cooperation::default();
+    let mut research = Research::default();
+    let mut scripts = Vec::new();
+    let mut serve = None;
+    let mut limits = Limits::default();
+    let mut stuck_ticks = 100;
+    let mut tick_ms = 400;
+    let mut fast = false;
+    let mut render_every = 10;
+    let mut headless = false;
+    let mut render = RenderMode::Ansi;
+    let mut export = ExportOptions {
+        scale: 4,
+        ..ExportOptions::default()
+    };
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log" => log = iter.next().cloned(),
+            "--report" => report = iter.next().cloned(),
+            "--tick-report" => tick_report = iter.next().cloned(),
+            "--visits-csv" => visits_csv = iter.next().cloned(),
+            "--energy-csv" => energy_csv = iter.next().cloned(),
+            "--render" => {
+                if let Some(mode) = iter.next() {
+                    render = mode.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    });
+                }
+            }
+            "--mission" => {
+                if let Some(spec) = iter.next() {
+                    mission = Some(spec.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    }));
+                }
+            }
+            "--exploration" => {
+                if let Some(mode) = iter.next() {
+                    exploration = mode.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    });
+                }
+            }
+            "--allocation" => {
+                if let Some(mode) = iter.next() {
+                    allocation = mode.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    });
+                }
+            }
+            "--large-minerals" => {
+                cooperation.large_minerals =
+                    iter.next().and_then(|n| n.parse().ok()).unwrap_or(cooperation.large_minerals)
+            }
+            "--large-science" => {
+                cooperation.large_science = iter.next().and_then(|n| n.parse().ok()).unwrap_or(cooperation.large_science)
+            }
+            "--research" => research = Research::preset(),
+            "--analysis-ticks" => {
+                research.analysis_ticks = iter.next().and_then(|t| t.parse().ok()).unwrap_or(research.analysis_ticks)
+            }
+            "--sensor-range" => {
+                research.sensor_range = iter.next().and_then(|r| r.parse().ok()).or(research.sensor_range)
+            }
+            "--script" => {
+                if let Some(spec) = iter.next() {
+                    scripts.push(parse_script(spec).unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    }));
+                }
+            }
+            "--factions" => {
+                factions = iter.next().and_then(|n| n.parse().ok()).unwrap_or(factions).clamp(1, MAX_FACTIONS)
+            }
+            "--strategies" => {
+                strategies = iter
+                    .next()
+                    .map(|list| {
+                        list.split(',')
+                            .map(|s| {
+                                s.trim().parse::<Strategy>().unwrap_or_else(|e| {
+                                    eprintln!("{}", e);
+                                    std::process::exit(2);
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            "--station" => stations.extend(iter.next().and_then(|p| parse_position(p))),
+            "--outpost-cost" => outpost_cost = iter.next().and_then(|c| c.parse().ok()),
+            "--outpost-spacing" => {
+                outpost_spacing = iter.next().and_then(|s| s.parse().ok()).unwrap_or(outpost_spacing)
+            }
+            "--inventory" => {
+                if let Some(mode) = iter.next() {
+                    inventory = mode.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    });
+                }
+            }
+            "--dynamic" => dynamics = Dynamics::preset(),
+            "--regrow" => {
+                dynamics.energy_regrowth = parse_chance(iter.next()).unwrap_or(dynamics.energy_regrowth)
+            }
+            "--veins" => dynamics.mineral_veins = parse_chance(iter.next()).unwrap_or(dynamics.mineral_veins),
+            "--storms" => dynamics.storms = iter.next().and_then(|n| n.parse().ok()).unwrap_or(dynamics.storms),
+            "--storm-radius" => {
+                dynamics.storm_radius = iter.next().and_then(|r| r.parse().ok()).unwrap_or(dynamics.storm_radius)
+            }
+            "--storm-damage" => {
+                dynamics.storm_damage = iter.next().and_then(|d| d.parse().ok()).unwrap_or(dynamics.storm_damage)
+            }
+            "--wear-and-tear" => reliability = Reliability::preset(),
+            "--wear" => reliability.wear = parse_chance(iter.next()).unwrap_or(reliability.wear),
+            "--failure-rate" => {
+                reliability.failure_rate = parse_chance(iter.next()).unwrap_or(reliability.failure_rate)
+            }
+            "--hazard-damage" => {
+                reliability.hazard_damage = iter.next().and_then(|d| d.parse().ok()).unwrap_or(reliability.hazard_damage)
+            }
+            "--repair-rate" => {
+                // A robot waits at the station until repaired, so repairs can never stop entirely
+                reliability.repair_rate =
+                    iter.next().and_then(|r| r.parse().ok()).unwrap_or(reliability.repair_rate).max(1)
+            }
+            "--radio" => radio.range = iter.next().and_then(|r| r.parse().ok()).unwrap_or(radio.range),
+            "--latency" => radio.latency = iter.next().and_then(|l| l.parse().ok()).unwrap_or(radio.latency),
+            "--packet-loss" => radio.loss = parse_chance(iter.next()).unwrap_or(radio.loss),
+            "--telemetry-every" => {
+                radio.interval = iter.next().and_then(|n| n.parse().ok()).unwrap_or(radio.interval).max(1)
+            }
+            "--relay" => radio.relays.extend(iter.next().and_then(|p| parse_position(p))),
+            "--deposit" => stigmergy.deposit = iter.next().and_then(|d| d.parse().ok()).unwrap_or(stigmergy.deposit),
+            "--evaporation" => stigmergy.evaporation = parse_chance(iter.next()).unwrap_or(stigmergy.evaporation),
+            "--max-ticks" => limits.max_ticks = iter.next().and_then(|t| t.parse().ok()),
+            "--timeout" => {
+                limits.timeout = iter
+                    .next()
+                    .and_then(|t| t.parse::<f64>().ok())
+                    .map(Duration::from_secs_f64)
+            }
+            "--stuck-ticks" => stuck_ticks = iter.next().and_then(|t| t.parse().ok()).unwrap_or(stuck_ticks),
+            "--tick-ms" => tick_ms = iter.next().and_then(|t| t.parse().ok()).unwrap_or(tick_ms),
+            "--fast" => fast = true,
+            "--render-every" => render_every = iter.next().and_then(|n| n.parse().ok()).unwrap_or(render_every),
+            "--headless" => headless = true,
+            "--serve" => serve = iter.next().cloned(),
+            "--png-dir" => export.png_dir = iter.next().map(Into::into),
+            "--gif" => export.gif = iter.next().map(Into::into),
+            "--final-png" => export.final_png = iter.next().map(Into::into),
+            "--heatmap-png" => export.heatmap_png = iter.next().map(Into::into),
+            "--energy-png" => export.energy_png = iter.next().map(Into::into),
+            "--scale" => export.scale = iter.next().and_then(|s| s.parse().ok()).unwrap_or(export.scale),
+            _ => positional.push(arg),
+        }
+    }
+
+    let width = positional.first().and_then(|w| w.parse().ok()).unwrap_or(default_width);
+    let height = positional.get(1).and_then(|h| h.parse().ok()).unwrap_or(default_height);
+
+    Command::Run(Box::new(RunOptions {
+        width,
+        height,
+        log,
+        report,
+        tick_report,
+        visits_csv,
+        energy_csv,
+        mission,
+        strategy: strategies.first().copied().unwrap_or_default(),
+        rivals: (1..factions.max(strategies.len()).min(MAX_FACTIONS))
+            .map(|faction| Faction {
+                swarm: Swarm::Random(3),
+                strategy: strategies.get(faction).copied().unwrap_or_default(),
+            })
+            .collect(),
+        stations,
+        expansion: outpost_cost.map(|cost| Expansion {
+            cost,
+            inventory,
+            spacing: outpost_spacing,
+        }),
+        dynamics,
+        reliability,
+        radio,
+        stigmergy,
+        exploration,
+        allocation,
+        cooperation,
+        research,
+        scripts,
+        serve,
+        limits,
+        stuck_ticks,
+        tick_ms,
+        fast,
+        render_every,
+        headless,
+        render: if headless { RenderMode::Null } else { render },
+        export,
+    }))
+}
+
+fn parse_sweep(args: &[String]) -> SweepOptions {
+    let mut grid = SweepGrid::default();
+    let mut threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut output = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let Some(value) = iter.next() else {
+            break;
+        };
+        match arg.as_str() {
+            "--explorers" => grid.explorers = parse_list(value, grid.explorers),
+            "--miners" => grid.miners = parse_list(value, grid.miners),
+            "--scientists" => grid.scientists = parse_list(value, grid.scientists),
+            "--densities" => grid.densities = parse_list(value, grid.densities),
+            "--sizes" => {
+                grid.sizes = value
+                    .split(',')
+                    .map(|size| {
+                        parse_size(size).unwrap_or_else(|| {
+                            eprintln!("invalid map size '{}' (expected WIDTHxHEIGHT, both at least 1)", size);
+                            std::process::exit(2);
+                        })
+                    })
+                    .collect();
+            }
+            "--seeds" => {
+                if let Ok(count) = value.parse::<u32>() {
+                    grid.seeds = (0..count).collect();
+                }
+            }
+            "--max-ticks" => grid.max_ticks = value.parse().unwrap_or(grid.max_ticks),
+            "--threads" => threads = value.parse().unwrap_or(threads),
+            "--output" => output = Some(value.clone()),
+            _ => {}
+        }
+    }
+
+    SweepOptions { grid, threads, output }
+}
+
+fn parse_replay(args: &[String]) -> Command {
+    let mut path = "events.jsonl".to_string();
+    let mut render = RenderMode::Ansi;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--render" => {
+                if let Some(mode) = iter.next() {
+                    render = mode.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    });
+                }
+            }
+            _ => path = arg.clone(),
+        }
+    }
+
+    Command::Replay { path, render }
+}
+
+fn parse_control(args: &[String]) -> ControlOptions {
+    let mut episode = Episode::default();
+    let mut listen = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let Some(value) = iter.next() else {
+            break;
+        };
+        match arg.as_str() {
+            "--seed" => episode.seed = value.parse().unwrap_or(episode.seed),
+            "--size" => {
+                if let Some((width, height)) = parse_size(value) {
+                    episode.width = width;
+                    episode.height = height;
+                }
+            }
+            "--robots" => episode.robots = value.parse().unwrap_or(episode.robots),
+            "--max-ticks" => episode.max_ticks = value.parse().unwrap_or(episode.max_ticks),
+            "--listen" => listen = Some(value.clone()),
+            _ => {}
+        }
+    }
+
+    ControlOptions { episode, listen }
+}
+
+// Parses a comma-separated list, keeping `default` when nothing valid is given
+fn parse_list<T: std::str::FromStr>(value: &str, default: Vec<T>) -> Vec<T> {
+    let values: Vec<T> = value.split(',').filter_map(|v| v.trim().parse().ok()).collect();
+    if values.is_empty() {
+        default
+    } else {
+        values
+    }
+}
+
+// A map size given as `WIDTHxHEIGHT`, neither of them 0
+fn parse_size(value: &str) -> Option<(usize, usize)> {
+    let (w, h) = value.split_once('x')?;
+    let size = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+// A map position given as `X,Y`
+fn parse_position(value: &str) -> Option<(usize, usize)> {
+    let (x, y) = value.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+// `path` or `type=path`
+fn parse_script(value: &str) -> Result<(Option<RobotType>, String), String> {
+    match value.split_once('=') {
+        Some((robot_type, path)) => Ok((Some(parse_robot_type(robot_type)?), path.to_string())),
+        None => Ok((None, value.to_string())),
+    }
+}
+
+// A probability between 0 and 1
+fn parse_chance(value: Option<&String>) -> Option<f64> {
+    value.and_then(|v| v.parse().ok()).filter(|p| (0.0..=1.0).contains(p))
+}
@@ -0,0 +1,168 @@
+use crate::event::Event;
+use crate::map::{Map, Tile};
+use crate::robot::{Robot, RobotType, Strategy, Task};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+// How many ordinary resources are turned into large sites at the start; none by default
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Cooperation {
+    pub large_minerals: usize,
+    pub large_science: usize,
+}
+
+// The robot types that must stand on a large site together, and what each of them carries home
+pub fn crew(tile: Tile) -> Option<(&'static [RobotType], Tile)> {
+    match tile {
+        Tile::LargeMineral => Some((&[RobotType::Miner, RobotType::Miner], Tile::Mineral)),
+        Tile::LargeScientific => Some((&[RobotType::Miner, RobotType::Scientist], Tile::Scientific)),
+        _ => None,
+    }
+}
+
+// Turns randomly chosen minerals and scientific sites into large ones
+pub fn place_sites(map: &mut Map, cooperation: Cooperation, seed: u32) {
+    if cooperation == Cooperation::default() {
+        return;
+    }
+    let mut rng = StdRng::seed_from_u64(seed as u64 ^ 0xc00b_0b5e);
+    for (tile, large, count) in [
+        (Tile::Mineral, Tile::LargeMineral, cooperation.large_minerals),
+        (Tile::Scientific, Tile::LargeScientific, cooperation.large_science),
+    ] {
+        let mut cells: Vec<(usize, usize)> = (0..map.height)
+            .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| map.grid[y][x] == tile)
+            .collect();
+        cells.shuffle(&mut rng);
+        for (x, y) in cells.into_iter().take(count) {
+            map.grid[y][x] = large;
+        }
+    }
+}
+
+// Recruits a crew for every large site and lets complete crews extract it
+pub struct Crews {
+    pub cooperation: Cooperation,
+    // The site each robot was sent to last tick
+    members: Vec<Option<(usize, usize)>>,
+    // Large sites extracted so far
+    pub completed: usize,
+}
+
+impl Crews {
+    pub fn new(cooperation: Cooperation, robots: usize) -> Self {
+        Self {
+            cooperation,
+            members: vec![None; robots],
+            completed: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cooperation.large_minerals > 0 || self.cooperation.large_science > 0
+    }
+
+    pub fn site(&self, robot: usize) -> Option<(usize, usize)> {
+        self.members.get(robot).copied().flatten()
+    }
+
+    // Sends the nearest free robots of the right types to each site, one crew per faction.
+    // Runs after the auction, so crew duty overrides any resource the robot was given there.
+    pub fn recruit(&mut self, map: &Map, robots: &mut [Robot]) {
+        for (robot, member) in robots.iter_mut().zip(self.members.iter_mut()) {
+            if let Some((x, y)) = member.take() {
+                if robot.task == Task::Assigned(x, y) {
+                    robot.task = Task::Unmanaged;
+                }
+            }
+        }
+
+        let factions = robots.iter().map(|r| r.faction + 1).max().unwrap_or(0);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let Some((roles, _)) = crew(map.grid[y][x]) else {
+                    continue;
+                };
+                for faction in 0..factions {
+                    let mut picked: Vec<usize> = Vec::new();
+                    for &role in roles {
+                        let nearest = robots
+                            .iter()
+                            .filter(|r| r.faction == faction && r.robot_type == role && !picked.contains(&r.id))
+                            .filter(|r| self.members[r.id].is_none() && available(r, (x, y)))
+                            .min_by_key(|r| (r.x.abs_diff(x) + r.y.abs_diff(y), r.id));
+                        match nearest {
+                            Some(robot) => picked.push(robot.id),
+                            None => break,
+                        }
+                    }
+                    // A partial crew would only wait around, so nobody is sent until all roles are filled
+                    if picked.len() == roles.len() {
+                        for id in picked {
+                            self.members[id] = Some((x, y));
+                            robots[id].task = Task::Assigned(x, y);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Extracts every site whose full crew is standing on it; each member carries one resource home
+    pub fn extract(&mut self, map: &mut Map, robots: &mut [Robot]) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        for id in 0..robots.len() {
+            let Some((x, y)) = self.members[id] else {
+                continue;
+            };
+            let Some((roles, yields)) = crew(map.grid[y][x]) else {
+                continue;
+            };
+            let faction = robots[id].faction;
+            let mut present: Vec<usize> = Vec::new();
+            for &role in roles {
+                let found = robots.iter().find(|r| {
+                    r.faction == faction
+                        && r.robot_type == role
+                        && (r.x, r.y) == (x, y)
+                        && !r.is_broken()
+                        && self.members[r.id] == Some((x, y))
+                        && !present.contains(&r.id)
+                });
+                match found {
+                    Some(robot) => present.push(robot.id),
+                    None => break,
+                }
+            }
+            if present.len() < roles.len() {
+                continue;
+            }
+
+            let tile = map.grid[y][x];
+            map.grid[y][x] = Tile::Empty;
+            self.completed += 1;
+            for &member in &present {
+                robots[member].discoveries.push((x, y, yields));
+                robots[member].task = Task::Unmanaged;
+                self.members[member] = None;
+                events.push(Event::ResourceHarvested { robot: member, x, y, tile: yields });
+            }
+            events.push(Event::SiteExtracted { x, y, tile, robots: present });
+        }
+
+        events
+    }
+}
+
+// Whether the robot is free for crew duty and has the energy to reach the site
+fn available(robot: &Robot, (x, y): (usize, usize)) -> bool {
+    robot.strategy == Strategy::Standard
+        && !robot.returning
+        && !robot.is_broken()
+        && robot.energy > 5
+        && robot.energy >= robot.x.abs_diff(x) + robot.y.abs_diff(y)
+}
@@ -0,0 +1,133 @@
+use crate::event::{Event, Record};
+use crate::map::Tile;
+use crate::protocol::{Episode, Observation};
+use crate::robot::Command;
+use crate::simulation::{Simulation, SimulationConfig, Swarm};
+
+pub type Reward = f64;
+
+// Reward shaping; the reward of a step is the weighted sum of what happened during it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rewards {
+    // Per resource of each type delivered to a station
+    pub energy: f64,
+    pub mineral: f64,
+    pub scientific: f64,
+    // Per unit of energy the robots spent, usually negative
+    pub energy_used: f64,
+    // Every tick, negative to favour short episodes
+    pub tick: f64,
+}
+
+impl Default for Rewards {
+    fn default() -> Self {
+        Self {
+            energy: 1.0,
+            mineral: 1.0,
+            scientific: 1.0,
+            energy_used: -0.01,
+            tick: 0.0,
+        }
+    }
+}
+
+// A reinforcement-learning environment around `Simulation`, one episode at a time
+pub struct Env {
+    pub episode: Episode,
+    pub rewards: Rewards,
+    simulation: Simulation,
+    // Energy the robots had spent by the end of the last step
+    energy_used: usize,
+}
+
+impl Env {
+    // Starts the first episode with `episode.seed`
+    pub fn new(episode: Episode, rewards: Rewards) -> Result<Self, String> {
+        let simulation = start(&episode)?;
+        Ok(Self {
+            episode,
+            rewards,
+            energy_used: energy_used(&simulation),
+            simulation,
+        })
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn reset(&mut self, seed: u32) -> Result<Observation, String> {
+        let episode = Episode { seed, ..self.episode };
+        self.simulation = start(&episode)?;
+        self.episode = episode;
+        self.energy_used = energy_used(&self.simulation);
+        Ok(self.observe())
+    }
+
+    // The current state, with the events since the last observation
+    pub fn observe(&mut self) -> Observation {
+        let done = self.is_done();
+        Observation::new(&mut self.simulation, done)
+    }
+
+    // Gives each listed robot its command for the tick, then runs it. Robots without one follow
+    // the built-in behaviour. Once the episode is done nothing moves and the reward is 0.
+    pub fn step(&mut self, actions: &[(usize, Command)]) -> Result<(Observation, Reward, bool), String> {
+        if let Some(&(robot, _)) = actions.iter().find(|&&(robot, _)| robot >= self.simulation.robots.len()) {
+            return Err(format!("unknown robot #{}", robot));
+        }
+        if self.is_done() {
+            return Ok((self.observe(), 0.0, true));
+        }
+
+        for &(robot, command) in actions {
+            self.simulation.robots[robot].command = Some(command);
+        }
+        self.simulation.step();
+
+        let observation = self.observe();
+        let reward = self.reward(&observation.events);
+        let done = observation.done;
+        Ok((observation, reward, done))
+    }
+
+    // The swarm is parked at its stations or the episode ran out of ticks
+    pub fn is_done(&self) -> bool {
+        self.simulation.tick >= self.episode.max_ticks || (self.simulation.tick > 0 && self.simulation.is_finished())
+    }
+
+    // Scores this tick's deliveries and the energy spent since the last step
+    fn reward(&mut self, records: &[Record]) -> Reward {
+        let deliveries: f64 = records
+            .iter()
+            .filter(|record| record.tick == self.simulation.tick)
+            .map(|record| match record.event {
+                Event::DiscoveryDelivered { tile: Tile::Energy, .. } => self.rewards.energy,
+                Event::DiscoveryDelivered { tile: Tile::Mineral, .. } => self.rewards.mineral,
+                Event::DiscoveryDelivered { tile: Tile::Scientific, .. } => self.rewards.scientific,
+                _ => 0.0,
+            })
+            .sum();
+        let total = energy_used(&self.simulation);
+        let spent = total - self.energy_used;
+        self.energy_used = total;
+        deliveries + spent as f64 * self.rewards.energy_used + self.rewards.tick
+    }
+}
+
+fn energy_used(simulation: &Simulation) -> usize {
+    simulation.robots.iter().map(|r| r.energy_used).sum()
+}
+
+fn start(episode: &Episode) -> Result<Simulation, String> {
+    if episode.width == 0 || episode.height == 0 {
+        return Err("the map needs a width and height of at least 1".to_string());
+    }
+    if episode.robots == 0 {
+        return Err("an episode needs at least one robot".to_string());
+    }
+    Ok(Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Random(episode.robots),
+        ..SimulationConfig::new(episode.seed, episode.width, episode.height)
+    }))
+}
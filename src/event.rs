@@ -0,0 +1,178 @@
+use crate::cooperation::Cooperation;
+use crate::map::{ResourceDensity, Tile};
+use crate::research::Upgrade;
+use crate::robot::{Fault, RobotType};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum Event {
+    RunStarted {
+        seed: u32,
+        width: usize,
+        height: usize,
+        station_x: usize,
+        station_y: usize,
+        #[serde(default)]
+        density: ResourceDensity,
+        #[serde(default)]
+        cooperation: Cooperation,
+    },
+    RobotBuilt {
+        robot: usize,
+        robot_type: RobotType,
+        x: usize,
+        y: usize,
+        #[serde(default)]
+        faction: usize,
+    },
+    RobotMoved {
+        robot: usize,
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    ResourceHarvested {
+        robot: usize,
+        x: usize,
+        y: usize,
+        tile: Tile,
+    },
+    DiscoveryDelivered {
+        robot: usize,
+        x: usize,
+        y: usize,
+        tile: Tile,
+    },
+    RobotStranded {
+        robot: usize,
+        x: usize,
+        y: usize,
+    },
+    // Regrown energy or a new mineral vein
+    ResourceSpawned {
+        x: usize,
+        y: usize,
+        tile: Tile,
+    },
+    // Energy lost to a storm
+    RobotDamaged {
+        robot: usize,
+        x: usize,
+        y: usize,
+        amount: usize,
+    },
+    ComponentFailed {
+        robot: usize,
+        fault: Fault,
+    },
+    // Health reached zero; the robot no longer moves
+    RobotBrokeDown {
+        robot: usize,
+        x: usize,
+        y: usize,
+    },
+    // Back to full health with every fault fixed
+    RobotRepaired {
+        robot: usize,
+        x: usize,
+        y: usize,
+    },
+    // A radio report from `robot`, sent at tick `sent`, reached a station over `hops` hops
+    TelemetryReceived {
+        robot: usize,
+        station: usize,
+        x: usize,
+        y: usize,
+        sent: u64,
+        hops: usize,
+    },
+    // A radio report dropped on the way to the station
+    MessageLost {
+        robot: usize,
+        hops: usize,
+    },
+    // A large site was extracted by the crew of `robots` standing on it together
+    SiteExtracted {
+        x: usize,
+        y: usize,
+        tile: Tile,
+        robots: Vec<usize>,
+    },
+    // A Scientist's sample was turned into research points at the station
+    ResearchDelivered {
+        robot: usize,
+        points: usize,
+    },
+    UpgradeResearched {
+        faction: usize,
+        upgrade: Upgrade,
+    },
+    // The station's auction gave `robot` the resource at (x, y)
+    TaskAssigned {
+        robot: usize,
+        x: usize,
+        y: usize,
+    },
+    // A station other than the first: a rival home or pre-placed at tick 0, or built from minerals later
+    StationBuilt {
+        station: usize,
+        x: usize,
+        y: usize,
+        #[serde(default)]
+        faction: usize,
+    },
+    // No progress since tick `since`; reported by the stuck-robot detector
+    RobotStuck {
+        robot: usize,
+        x: usize,
+        y: usize,
+        since: u64,
+    },
+}
+
+// One line of the event log: the tick an event happened on plus the event itself
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub tick: u64,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+pub struct EventLog<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> EventLog<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")
+    }
+
+    pub fn write_all(&mut self, records: &[Record]) -> io::Result<()> {
+        for record in records {
+            self.write(record)?;
+        }
+        self.writer.flush()
+    }
+}
+
+pub fn read_log<R: BufRead>(reader: R) -> io::Result<Vec<Record>> {
+    let mut records = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
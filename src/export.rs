@@ -0,0 +1,315 @@
+use crate::heatmap::Heatmap;
+use crate::map::{Map, Tile};
+use crate::robot::{Robot, RobotType};
+use crate::simulation::Simulation;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+const EMPTY: u8 = 0;
+const OBSTACLE: u8 = 1;
+const ENERGY: u8 = 2;
+const MINERAL: u8 = 3;
+const SCIENTIFIC: u8 = 4;
+const STATION: u8 = 5;
+const EXPLORER: u8 = 6;
+const MINER: u8 = 7;
+const SCIENTIST: u8 = 8;
+const LARGE_MINERAL: u8 = 12;
+const LARGE_SCIENTIFIC: u8 = 13;
+const HEAT: u8 = 16;
+const HEAT_LEVELS: u8 = 16;
+
+// Indexed colours shared by PNG and GIF output: tiles, robots, then a cold-to-hot ramp
+pub const PALETTE: [[u8; 3]; 32] = [
+    [235, 235, 225],
+    [70, 70, 70],
+    [240, 200, 40],
+    [60, 170, 80],
+    [60, 190, 210],
+    [190, 60, 190],
+    [40, 90, 230],
+    [230, 120, 30],
+    [200, 40, 60],
+    [150, 175, 245],
+    [245, 190, 140],
+    [235, 150, 165],
+    [30, 110, 50],
+    [30, 120, 150],
+    [0, 0, 0],
+    [0, 0, 0],
+    [20, 20, 60],
+    [25, 30, 100],
+    [30, 45, 140],
+    [35, 70, 170],
+    [40, 100, 190],
+    [45, 135, 195],
+    [55, 165, 180],
+    [80, 190, 150],
+    [120, 205, 110],
+    [165, 215, 75],
+    [205, 215, 50],
+    [235, 200, 40],
+    [245, 165, 35],
+    [245, 120, 30],
+    [235, 75, 30],
+    [215, 30, 30],
+];
+
+// Palette-indexed raster image
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![EMPTY; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn rgb(&self, x: usize, y: usize) -> [u8; 3] {
+        PALETTE[self.get(x, y) as usize]
+    }
+
+    // Fills the square of side `size` starting `inset` pixels into map cell (x, y)
+    fn fill_cell(&mut self, x: usize, y: usize, scale: usize, inset: usize, color: u8) {
+        let size = scale - 2 * inset;
+        for py in 0..size {
+            let row = (y * scale + inset + py) * self.width;
+            let start = row + x * scale + inset;
+            self.pixels[start..start + size].fill(color);
+        }
+    }
+}
+
+fn tile_color(tile: Tile) -> u8 {
+    match tile {
+        Tile::Empty => EMPTY,
+        Tile::Obstacle => OBSTACLE,
+        Tile::Energy => ENERGY,
+        Tile::Mineral => MINERAL,
+        Tile::Scientific => SCIENTIFIC,
+        Tile::Station => STATION,
+        Tile::LargeMineral => LARGE_MINERAL,
+        Tile::LargeScientific => LARGE_SCIENTIFIC,
+    }
+}
+
+fn robot_color(robot_type: RobotType) -> u8 {
+    match robot_type {
+        RobotType::Explorer => EXPLORER,
+        RobotType::Miner => MINER,
+        RobotType::Scientist => SCIENTIST,
+    }
+}
+
+// Light version of the robot colour, used for the path it left behind
+fn trail_color(robot_type: RobotType) -> u8 {
+    robot_color(robot_type) + 3
+}
+
+// Every cell each robot has stood on, coloured by the last robot type there
+#[derive(Default)]
+pub struct Trails {
+    cells: HashMap<(usize, usize), RobotType>,
+}
+
+impl Trails {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, robots: &[Robot]) {
+        for robot in robots {
+            self.cells.insert((robot.x, robot.y), robot.robot_type);
+        }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.cells.contains_key(&(x, y))
+    }
+}
+
+// Draws the map with robot trails as small dots and robots as filled cells.
+// Each map cell becomes a `scale`×`scale` block of pixels.
+pub fn render_frame(map: &Map, robots: &[Robot], trails: &Trails, scale: usize) -> Image {
+    let scale = scale.max(1);
+    let mut image = Image::new(map.width * scale, map.height * scale);
+
+    for (y, row) in map.grid.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            image.fill_cell(x, y, scale, 0, tile_color(tile));
+        }
+    }
+    for (&(x, y), &robot_type) in &trails.cells {
+        if map.grid[y][x] == Tile::Empty {
+            image.fill_cell(x, y, scale, scale / 3, trail_color(robot_type));
+        }
+    }
+    for robot in robots {
+        image.fill_cell(robot.x, robot.y, scale, 0, robot_color(robot.robot_type));
+    }
+
+    image
+}
+
+// Colours each cell by its heatmap value on a logarithmic ramp;
+// obstacles stay dark grey and zero cells keep the background colour
+pub fn render_heatmap(map: &Map, heatmap: &Heatmap, scale: usize) -> Image {
+    let scale = scale.max(1);
+    let mut image = Image::new(map.width * scale, map.height * scale);
+    let max = heatmap.max().max(1) as f64;
+
+    for (y, row) in map.grid.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            let value = heatmap.get(x, y);
+            let color = if tile == Tile::Obstacle {
+                OBSTACLE
+            } else if value == 0 {
+                EMPTY
+            } else {
+                let level = ((value as f64).ln_1p() / max.ln_1p() * (HEAT_LEVELS - 1) as f64).round() as u8;
+                HEAT + level.min(HEAT_LEVELS - 1)
+            };
+            image.fill_cell(x, y, scale, 0, color);
+        }
+    }
+
+    image
+}
+
+fn palette_bytes() -> Vec<u8> {
+    PALETTE.iter().flatten().copied().collect()
+}
+
+pub fn write_png<W: Write>(writer: W, image: &Image) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette_bytes());
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&image.pixels).map_err(io::Error::other)
+}
+
+// Streams frames into an animated GIF so a whole run never has to be held in memory
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: u16,
+    height: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    pub fn new(writer: W, width: usize, height: usize) -> io::Result<Self> {
+        let (width, height) = (width as u16, height as u16);
+        let mut encoder = gif::Encoder::new(writer, width, height, &palette_bytes()).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+        Ok(Self { encoder, width, height })
+    }
+
+    // `delay` is in hundredths of a second
+    pub fn add_frame(&mut self, image: &Image, delay: u16) -> io::Result<()> {
+        let mut frame = gif::Frame::from_indexed_pixels(self.width, self.height, image.pixels.as_slice(), None);
+        frame.delay = delay;
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    // One PNG per tick, named `tick_00000.png`
+    pub png_dir: Option<PathBuf>,
+    pub gif: Option<PathBuf>,
+    pub final_png: Option<PathBuf>,
+    pub heatmap_png: Option<PathBuf>,
+    pub energy_png: Option<PathBuf>,
+    pub scale: usize,
+}
+
+impl ExportOptions {
+    pub fn is_empty(&self) -> bool {
+        self.png_dir.is_none() && self.gif.is_none() && self.final_png.is_none() && self.heatmap_png.is_none()
+            && self.energy_png.is_none()
+    }
+}
+
+// Captures a simulation tick by tick into the requested image files
+pub struct Exporter {
+    options: ExportOptions,
+    trails: Trails,
+    gif: Option<GifWriter<BufWriter<File>>>,
+}
+
+impl Exporter {
+    pub fn new(options: ExportOptions, simulation: &Simulation) -> io::Result<Self> {
+        let map = &simulation.map;
+        let scale = options.scale.max(1);
+
+        if let Some(dir) = &options.png_dir {
+            fs::create_dir_all(dir)?;
+        }
+        let gif = match &options.gif {
+            Some(path) => Some(GifWriter::new(
+                BufWriter::new(File::create(path)?),
+                map.width * scale,
+                map.height * scale,
+            )?),
+            None => None,
+        };
+
+        let mut exporter = Self {
+            trails: Trails::new(),
+            options: ExportOptions { scale, ..options },
+            gif,
+        };
+        exporter.capture(simulation)?;
+        Ok(exporter)
+    }
+
+    pub fn capture(&mut self, simulation: &Simulation) -> io::Result<()> {
+        self.trails.record(&simulation.robots);
+
+        if self.options.png_dir.is_none() && self.gif.is_none() {
+            return Ok(());
+        }
+        let image = render_frame(&simulation.map, &simulation.robots, &self.trails, self.options.scale);
+
+        if let Some(dir) = &self.options.png_dir {
+            let path = dir.join(format!("tick_{:05}.png", simulation.tick));
+            write_png(BufWriter::new(File::create(path)?), &image)?;
+        }
+        if let Some(gif) = self.gif.as_mut() {
+            gif.add_frame(&image, 10)?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(self, simulation: &Simulation) -> io::Result<()> {
+        let scale = self.options.scale;
+
+        if let Some(path) = &self.options.final_png {
+            let image = render_frame(&simulation.map, &simulation.robots, &self.trails, scale);
+            write_png(BufWriter::new(File::create(path)?), &image)?;
+        }
+        if let Some(path) = &self.options.heatmap_png {
+            let image = render_heatmap(&simulation.map, &simulation.heatmaps.visits, scale);
+            write_png(BufWriter::new(File::create(path)?), &image)?;
+        }
+        if let Some(path) = &self.options.energy_png {
+            let image = render_heatmap(&simulation.map, &simulation.heatmaps.energy, scale);
+            write_png(BufWriter::new(File::create(path)?), &image)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use crate::map::{Map, Tile};
+use crate::robot::{Robot, RobotType, Strategy};
+use crate::simulation::faction_name;
+use std::collections::VecDeque;
+
+// How standard Explorers pick where to go
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Exploration {
+    // A random direction every tick
+    #[default]
+    Random,
+    // The nearest frontier between known and unknown cells, split between the faction's explorers
+    Frontier,
+}
+
+impl std::str::FromStr for Exploration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(Exploration::Random),
+            "frontier" => Ok(Exploration::Frontier),
+            _ => Err(format!("unknown exploration '{}' (expected random or frontier)", s)),
+        }
+    }
+}
+
+// Every robot sees the cells within this many steps in any direction
+pub const SENSOR_RANGE: usize = 2;
+// Frontiers within this radius of another explorer's target are treated as claimed
+const CLAIM_RADIUS: usize = SENSOR_RANGE;
+
+// What each faction has seen of the map, and the frontier each explorer is heading for
+pub struct Frontiers {
+    pub exploration: Exploration,
+    width: usize,
+    height: usize,
+    known: Vec<Vec<bool>>,
+    targets: Vec<Option<(usize, usize)>>,
+}
+
+impl Frontiers {
+    pub fn new(exploration: Exploration, width: usize, height: usize, factions: usize, robots: usize) -> Self {
+        Self {
+            exploration,
+            width,
+            height,
+            known: vec![vec![false; width * height]; factions],
+            targets: vec![None; robots],
+        }
+    }
+
+    pub fn is_known(&self, faction: usize, x: usize, y: usize) -> bool {
+        self.known[faction][y * self.width + x]
+    }
+
+    // Percentage of the map the faction has seen
+    pub fn known_percent(&self, faction: usize) -> f64 {
+        let known = self.known[faction].iter().filter(|&&k| k).count();
+        known as f64 * 100.0 / self.known[faction].len().max(1) as f64
+    }
+
+    // The frontier cell the robot is heading for
+    pub fn target(&self, robot: usize) -> Option<(usize, usize)> {
+        self.targets.get(robot).copied().flatten()
+    }
+
+    // A known, passable cell next to at least one unknown cell
+    pub fn is_frontier(&self, map: &Map, faction: usize, x: usize, y: usize) -> bool {
+        self.is_known(faction, x, y)
+            && map.grid[y][x] != Tile::Obstacle
+            && self.neighbours(x, y).any(|(nx, ny)| !self.is_known(faction, nx, ny))
+    }
+
+    // Lets every robot look around, then gives each explorer a frontier and the next step towards it
+    pub fn tick(&mut self, map: &Map, robots: &mut [Robot]) {
+        if self.exploration != Exploration::Frontier {
+            return;
+        }
+
+        for robot in robots.iter() {
+            let known = &mut self.known[robot.faction];
+            for y in robot.y.saturating_sub(SENSOR_RANGE)..=(robot.y + SENSOR_RANGE).min(self.height - 1) {
+                for x in robot.x.saturating_sub(SENSOR_RANGE)..=(robot.x + SENSOR_RANGE).min(self.width - 1) {
+                    known[y * self.width + x] = true;
+                }
+            }
+        }
+
+        let mut claimed: Vec<(usize, (usize, usize))> = Vec::new();
+        for robot in robots.iter_mut() {
+            robot.waypoint = None;
+            if robot.robot_type != RobotType::Explorer
+                || robot.strategy != Strategy::Standard
+                || robot.returning
+                || robot.is_broken()
+            {
+                self.targets[robot.id] = None;
+                continue;
+            }
+
+            let taken: Vec<(usize, usize)> = claimed
+                .iter()
+                .filter(|&&(faction, _)| faction == robot.faction)
+                .map(|&(_, target)| target)
+                .collect();
+            match self.plan(map, robot, &taken) {
+                Some((target, step)) => {
+                    claimed.push((robot.faction, target));
+                    self.targets[robot.id] = Some(target);
+                    robot.waypoint = Some(step);
+                }
+                None => self.targets[robot.id] = None,
+            }
+        }
+    }
+
+    // Breadth-first search over known passable cells. Keeps the current target while it is
+    // still a frontier, otherwise takes the nearest frontier away from the `taken` ones.
+    fn plan(&self, map: &Map, robot: &Robot, taken: &[(usize, usize)]) -> Option<((usize, usize), (usize, usize))> {
+        let faction = robot.faction;
+        let start = robot.y * self.width + robot.x;
+        let mut parent = vec![usize::MAX; self.width * self.height];
+        parent[start] = start;
+        let mut queue = VecDeque::from([(robot.x, robot.y)]);
+        let mut frontiers = Vec::new();
+
+        while let Some((x, y)) = queue.pop_front() {
+            if self.is_frontier(map, faction, x, y) {
+                frontiers.push((x, y));
+            }
+            for (nx, ny) in self.neighbours(x, y) {
+                let index = ny * self.width + nx;
+                if parent[index] == usize::MAX && self.is_known(faction, nx, ny) && map.is_passable(nx, ny) {
+                    parent[index] = y * self.width + x;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        let free = |&&(x, y): &&(usize, usize)| {
+            !taken.iter().any(|&(tx, ty)| tx.abs_diff(x).max(ty.abs_diff(y)) <= CLAIM_RADIUS)
+        };
+        let current = self.targets[robot.id].filter(|target| frontiers.contains(target) && free(&target));
+        let target = current
+            .or_else(|| frontiers.iter().find(free).copied())
+            .or_else(|| frontiers.first().copied())?;
+
+        // Walk back from the target to the cell right after the robot's own
+        let mut step = target.1 * self.width + target.0;
+        if step == start {
+            return None;
+        }
+        while parent[step] != start {
+            step = parent[step];
+        }
+        Some((target, (step % self.width, step / self.width)))
+    }
+
+    pub fn display_report(&self) {
+        if self.exploration != Exploration::Frontier {
+            return;
+        }
+
+        println!("\n🧭 Frontier exploration:");
+        println!("--------------------------");
+        for faction in 0..self.known.len() {
+            println!(" - {} has seen {:.1}% of the map", faction_name(faction), self.known_percent(faction));
+        }
+    }
+
+    fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        [(0, -1), (0, 1), (-1, 0), (1, 0)].into_iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < width)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < height)?;
+            Some((nx, ny))
+        })
+    }
+}
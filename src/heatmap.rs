@@ -0,0 +1,122 @@
+use crate::pheromone::Pheromones;
+use crate::robot::Robot;
+
+// A per-cell counter laid over the map grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    pub width: usize,
+    pub height: usize,
+    values: Vec<u32>,
+}
+
+impl Heatmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            values: vec![0; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u32 {
+        self.values[y * self.width + x]
+    }
+
+    pub fn add(&mut self, x: usize, y: usize, amount: u32) {
+        self.values[y * self.width + x] += amount;
+    }
+
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    pub fn max(&self) -> u32 {
+        self.values.iter().copied().max().unwrap_or(0)
+    }
+
+    // Sum over a block of cells, clipped to the grid
+    pub fn block_sum(&self, x: usize, y: usize, width: usize, height: usize) -> u32 {
+        let x_end = (x + width).min(self.width);
+        let y_end = (y + height).min(self.height);
+        if x >= x_end || y >= y_end {
+            return 0;
+        }
+        self.values[y * self.width..y_end * self.width]
+            .chunks(self.width)
+            .map(|row| row[x..x_end].iter().sum::<u32>())
+            .sum()
+    }
+
+    // Removes a `rate` fraction of every value, rounding up so faint traces disappear
+    pub fn evaporate(&mut self, rate: f64) {
+        for value in self.values.iter_mut() {
+            *value -= (*value as f64 * rate).ceil().min(*value as f64) as u32;
+        }
+    }
+
+    // One line per map row, comma-separated values per column
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.values.chunks(self.width.max(1)) {
+            let line: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            csv.push_str(&line.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmaps {
+    // Robot-ticks spent on each cell
+    pub visits: Heatmap,
+    // Energy robots spent while leaving each cell
+    pub energy: Heatmap,
+}
+
+impl Heatmaps {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            visits: Heatmap::new(width, height),
+            energy: Heatmap::new(width, height),
+        }
+    }
+
+    pub fn record_visits(&mut self, robots: &[Robot]) {
+        for robot in robots {
+            self.visits.add(robot.x, robot.y, 1);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Overlay {
+    #[default]
+    None,
+    Visits,
+    Energy,
+    Trail,
+    Explored,
+}
+
+impl Overlay {
+    pub fn next(self) -> Self {
+        match self {
+            Overlay::None => Overlay::Visits,
+            Overlay::Visits => Overlay::Energy,
+            Overlay::Energy => Overlay::Trail,
+            Overlay::Trail => Overlay::Explored,
+            Overlay::Explored => Overlay::None,
+        }
+    }
+
+    pub fn select<'a>(self, heatmaps: &'a Heatmaps, pheromones: &'a Pheromones) -> Option<&'a Heatmap> {
+        match self {
+            Overlay::None => None,
+            Overlay::Visits => Some(&heatmaps.visits),
+            Overlay::Energy => Some(&heatmaps.energy),
+            Overlay::Trail => Some(&pheromones.trail),
+            Overlay::Explored => Some(&pheromones.explored),
+        }
+    }
+}
@@ -1,3 +1,28 @@
 pub mod robot;
 pub mod map;
 pub mod station;
+pub mod event;
+pub mod simulation;
+pub mod replay;
+pub mod metrics;
+pub mod sweep;
+pub mod tui;
+pub mod viewport;
+pub mod render;
+pub mod export;
+pub mod heatmap;
+pub mod mission;
+pub mod watchdog;
+pub mod world;
+pub mod reliability;
+pub mod radio;
+pub mod pheromone;
+pub mod frontier;
+pub mod auction;
+pub mod cooperation;
+pub mod research;
+pub mod script;
+pub mod protocol;
+pub mod env;
+#[cfg(feature = "server")]
+pub mod server;
@@ -1,94 +1,355 @@
-mod map;
-mod robot;
-mod station;
-
-use map::{Map, Tile};
-use rand::{Rng, SeedableRng};
-use robot::initialize_robots;
-use station::Station;
+mod cli;
+
+use cli::{Command, ControlOptions, RunOptions, SweepOptions};
+use rand::Rng;
+use robot_swarm::event::{read_log, EventLog};
+use robot_swarm::export::Exporter;
+use robot_swarm::metrics::Metrics;
+use robot_swarm::mission::MissionStatus;
+use robot_swarm::protocol;
+use robot_swarm::replay::Replay;
+use robot_swarm::script::ScriptController;
+#[cfg(feature = "server")]
+use robot_swarm::server::Server;
+use robot_swarm::simulation::{Simulation, SimulationConfig, Swarm};
+use robot_swarm::station::display_stations;
+use robot_swarm::sweep::{results_csv, run_sweep};
+use robot_swarm::render::{self, RenderMode, Renderer};
+use robot_swarm::tui::{Control, Status};
+use robot_swarm::viewport::Viewport;
+use robot_swarm::watchdog::{StopReason, StuckDetector};
 use std::env;
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::thread::sleep;
-use std::time::Duration;
-use crossterm::{
-    execute,
-    cursor::{Hide, MoveTo, Show},
-    terminal::{Clear, ClearType},
-    style::Print,
-};
-use std::io::{stdout, Write};
+use std::net::TcpListener;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::io::{stdin, stdout};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    let default_width = 20;
-    let default_height = 10;
-    let width = args.get(1).and_then(|w| w.parse().ok()).unwrap_or(default_width);
-    let height = args.get(2).and_then(|h| h.parse().ok()).unwrap_or(default_height);
+    match cli::parse(&args) {
+        Command::Run(options) => run(*options),
+        Command::Replay { path, render } => replay(&path, render),
+        Command::Sweep(options) => sweep(options),
+        Command::Control(options) => control(options),
+    }
+}
 
+fn run(options: RunOptions) {
     let seed = rand::thread_rng().gen_range(0..10000);
-    let station_x = 0;
-    let station_y = height / 2;
-    let station = Arc::new(Mutex::new(Station::new(station_x, station_y)));
-    let map = Arc::new(Mutex::new(Map::new(seed, width, height)));
-    map.lock().unwrap().grid[station_y][station_x] = Tile::Station;
+    let count = 3;
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Random(count),
+        strategy: options.strategy,
+        rivals: options.rivals.clone(),
+        stations: options.stations.clone(),
+        expansion: options.expansion,
+        dynamics: options.dynamics,
+        reliability: options.reliability,
+        radio: options.radio.clone(),
+        stigmergy: options.stigmergy,
+        exploration: options.exploration,
+        allocation: options.allocation,
+        cooperation: options.cooperation,
+        research: options.research,
+        ..SimulationConfig::new(seed, options.width, options.height)
+    });
+    simulation.mission = options.mission.clone();
+    if !options.scripts.is_empty() {
+        let sources: Vec<_> = options
+            .scripts
+            .iter()
+            .map(|(robot_type, path)| {
+                let source = fs::read_to_string(path).unwrap_or_else(|e| {
+                    eprintln!("cannot read script {}: {}", path, e);
+                    std::process::exit(2);
+                });
+                (*robot_type, source)
+            })
+            .collect();
+        simulation.controller = Some(ScriptController::new(&sources, simulation.robots.len()).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }));
+    }
+    let mut metrics = Metrics::new(&simulation);
 
+    let mut log = options.log.map(|path| {
+        EventLog::new(BufWriter::new(File::create(path).expect("cannot create event log")))
+    });
 
-    let count = 3;
-    let robots = Arc::new(Mutex::new(initialize_robots(count, width, height, seed)));
+    let mut exporter = if options.export.is_empty() {
+        None
+    } else {
+        Some(Exporter::new(options.export.clone(), &simulation).expect("cannot start image export"))
+    };
 
-    let mut stdout = stdout();
-    execute!(stdout, Hide, Clear(ClearType::All)).unwrap();
+    #[cfg(feature = "server")]
+    let mut server = options.serve.as_deref().map(|address| {
+        let server = Server::start(address, &simulation).expect("cannot start observation server");
+        eprintln!("Watch the run on http://{}", server.address);
+        server
+    });
+    #[cfg(not(feature = "server"))]
+    if options.serve.is_some() {
+        eprintln!("--serve needs a build with `--features server`");
+        std::process::exit(2);
+    }
 
-    println!("\nStarting simulation...\n");
+    let mut status = Status::new(Duration::from_millis(options.tick_ms));
+    if options.fast || options.headless {
+        status.delay = Duration::ZERO;
+    }
+    if options.fast {
+        status.render_every = options.render_every.max(1);
+    }
 
-    loop {
-        sleep(Duration::from_millis(400));
+    let mut viewport = Viewport::default();
+    let mut renderer = render::create(options.render).expect("cannot initialise renderer");
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || {
+        // A second Ctrl-C means the graceful shutdown itself is stuck
+        if flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+    })
+    .expect("cannot install Ctrl-C handler");
+
+    let started = Instant::now();
+    let mut stuck = StuckDetector::new(&simulation, options.stuck_ticks);
+
+    let reason = loop {
+        if !options.headless && status.should_draw(simulation.tick) {
+            renderer
+                .draw(&simulation, &status, &mut viewport)
+                .expect("cannot draw simulation");
+        }
+
+        match wait_for_tick(renderer.as_mut(), &simulation, &mut status, &mut viewport) {
+            Advance::Quit => break StopReason::Quit,
+            // A live run cannot go back or skip ahead
+            Advance::Seek(_) => continue,
+            Advance::Next => {}
+        }
+        if interrupted.load(Ordering::SeqCst) {
+            break StopReason::Interrupted;
+        }
+
+        simulation.step();
+
+        let mut events = simulation.take_events();
+        let stuck_events = stuck.record(&simulation, &events);
+        events.extend(stuck_events);
+        metrics.record(&simulation, &events);
+        if let Some(log) = log.as_mut() {
+            log.write_all(&events).expect("cannot write event log");
+        }
+        if let Some(exporter) = exporter.as_mut() {
+            exporter.capture(&simulation).expect("cannot export frame");
+        }
+        #[cfg(feature = "server")]
+        if let Some(server) = server.as_mut() {
+            server.publish(&simulation, &events);
+        }
+
+        if simulation.is_finished() {
+            break StopReason::Finished;
+        }
+        if let Some(reason) = options.limits.check(&simulation, started) {
+            break reason;
+        }
+        if stuck.all_stuck() {
+            break StopReason::AllStuck;
+        }
+    };
 
-        let mut handles = vec![];
+    drop(renderer);
 
-        for i in 0..count {
-            let map_clone = Arc::clone(&map);
-            let robots_clone = Arc::clone(&robots);
-            let station_clone = Arc::clone(&station);
+    println!("\n{}.", reason);
+    if options.fast || options.headless {
+        let elapsed = started.elapsed().as_secs_f64();
+        println!(
+            "{} ticks in {:.3}s ({:.0} ticks/s)",
+            simulation.tick,
+            elapsed,
+            simulation.tick as f64 / elapsed.max(f64::EPSILON)
+        );
+    }
 
-            
-            let handle = thread::spawn(move || {
-                let mut rng = rand::rngs::StdRng::from_entropy();
-                let mut map = map_clone.lock().unwrap();
-                let mut robots_lock = robots_clone.lock().unwrap();
-                let mut station = station_clone.lock().unwrap();
-                let robot = &mut robots_lock[i];
+    display_stations(&simulation.stations);
+    if simulation.factions > 1 {
+        simulation.display_scores();
+    }
+    metrics.display_report();
+    stuck.display_report(&simulation);
+    simulation.network.display_report(&simulation.robots);
+    simulation.frontiers.display_report();
+    simulation.labs.display_report();
+    if let Some(controller) = &simulation.controller {
+        controller.display_report();
+    }
 
-                robot.tick(&mut map, &mut station, &mut rng, station_x, station_y);
-            });
+    if let Some(path) = &options.report {
+        let contents = if path.ends_with(".json") {
+            metrics.to_json()
+        } else {
+            metrics.robots_csv()
+        };
+        fs::write(path, contents).expect("cannot write report");
+    }
+    if let Some(path) = &options.tick_report {
+        fs::write(path, metrics.ticks_csv()).expect("cannot write tick report");
+    }
+    if let Some(path) = &options.visits_csv {
+        fs::write(path, simulation.heatmaps.visits.to_csv()).expect("cannot write visits heatmap");
+    }
+    if let Some(path) = &options.energy_csv {
+        fs::write(path, simulation.heatmaps.energy.to_csv()).expect("cannot write energy heatmap");
+    }
+    if let Some(exporter) = exporter {
+        exporter.finish(&simulation).expect("cannot export images");
+    }
 
-            handles.push(handle);
+    if let Some(mission) = &simulation.mission {
+        mission.display_progress(&simulation);
+        match mission.evaluate(&simulation) {
+            MissionStatus::Accomplished => println!("\nMission accomplished at tick {}.", simulation.tick),
+            MissionStatus::Failed(reason) => {
+                println!("\nMission failed: {}.", reason);
+                std::process::exit(1);
+            }
+            MissionStatus::InProgress => {
+                println!("\nMission incomplete: run stopped at tick {}.", simulation.tick);
+                std::process::exit(1);
+            }
         }
+    }
+    if reason == StopReason::Interrupted {
+        std::process::exit(130);
+    }
+}
+
+
+// What to show after `wait_for_tick`
+enum Advance {
+    Next,
+    Seek(i64),
+    Quit,
+}
+
+// Handles keyboard controls until the next tick is due or the user seeks or quits
+fn wait_for_tick(
+    renderer: &mut dyn Renderer,
+    simulation: &Simulation,
+    status: &mut Status,
+    viewport: &mut Viewport,
+) -> Advance {
+    let deadline = Instant::now() + status.delay;
 
-        for handle in handles {
-            handle.join().unwrap();
+    loop {
+        let timeout = if status.paused {
+            Duration::from_millis(100)
+        } else {
+            deadline.saturating_duration_since(Instant::now())
+        };
+
+        match renderer.poll_control(timeout).expect("cannot read terminal input") {
+            Some(Control::Quit) => return Advance::Quit,
+            Some(Control::Step) => return Advance::Next,
+            Some(Control::Seek(ticks)) => return Advance::Seek(ticks),
+            Some(Control::TogglePause) => status.paused = !status.paused,
+            Some(Control::Faster) => status.delay = (status.delay / 2).max(status.delay.min(Duration::from_millis(25))),
+            Some(Control::Slower) => {
+                status.delay = (status.delay * 2).clamp(Duration::from_millis(25), Duration::from_millis(3200))
+            }
+            Some(Control::Scroll(dx, dy)) => viewport.scroll(dx, dy, &simulation.map),
+            Some(Control::Follow) => viewport.cycle_follow(&simulation.robots),
+            Some(Control::ZoomIn) => viewport.zoom_in(),
+            Some(Control::ZoomOut) => viewport.zoom_out(),
+            Some(Control::Overlay) => viewport.overlay = viewport.overlay.next(),
+            None if !status.paused && Instant::now() >= deadline => return Advance::Next,
+            None => continue,
         }
 
-        let map_final = map.lock().unwrap();
-        let robots_final = robots.lock().unwrap();
-        map_final.display_map(&robots_final);
+        renderer
+            .draw(simulation, status, viewport)
+            .expect("cannot draw simulation");
+    }
+}
+
+fn replay(path: &str, mode: RenderMode) {
+    let file = File::open(path).expect("cannot open event log");
+    let records = read_log(BufReader::new(file)).expect("cannot read event log");
+    let mut replay = Replay::new(records).expect("event log does not start with RunStarted");
 
-        if robots_final
-        .iter()
-        .all(|r| r.returning == false && r.x == station_x && r.y == station_y)
-        {
+    let last_tick = replay.last_tick();
+    let mut tick = 0;
+    let mut status = Status::new(Duration::from_millis(400));
+    let mut viewport = Viewport::default();
+    let mut renderer = render::create(mode).expect("cannot initialise renderer");
+
+    loop {
+        let simulation = replay.seek(tick);
+        renderer
+            .draw(simulation, &status, &mut viewport)
+            .expect("cannot draw replay");
+        // Without a keyboard there is nobody to quit, so the replay ends with the log
+        if tick == last_tick && !renderer.is_interactive() {
             break;
         }
+
+        match wait_for_tick(renderer.as_mut(), simulation, &mut status, &mut viewport) {
+            Advance::Quit => break,
+            Advance::Next => tick = (tick + 1).min(last_tick),
+            Advance::Seek(ticks) => tick = tick.saturating_add_signed(ticks).min(last_tick),
+        }
     }
+}
+
+// Lets external programs drive the robots with line-delimited JSON, see `protocol.rs`
+fn control(options: ControlOptions) {
+    let Some(address) = &options.listen else {
+        protocol::serve(stdin().lock(), stdout().lock(), options.episode).expect("controller session failed");
+        return;
+    };
 
-    execute!(stdout, MoveTo(0, height as u16 + 2), Print("\nsimulation completed."), Show).unwrap();
+    let listener = TcpListener::bind(address).expect("cannot listen on the controller address");
+    eprintln!("Waiting for controllers on {}...", address);
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        if let Err(e) = protocol::serve(BufReader::new(reader), BufWriter::new(stream), options.episode) {
+            eprintln!("controller disconnected: {}", e);
+        }
+    }
+}
 
-    let station_final = station.lock().unwrap();
-    station_final.display_discoveries();
+fn sweep(options: SweepOptions) {
+    let points = options.grid.points().len();
+    eprintln!(
+        "Running {} parameter combinations x {} seeds on {} threads...",
+        points,
+        options.grid.seeds.len(),
+        options.threads
+    );
 
-    stdout.flush().unwrap();
+    let results = run_sweep(&options.grid, options.threads);
+    let csv = results_csv(&results);
 
-    sleep(Duration::from_secs(2));
-}
\ No newline at end of file
+    match &options.output {
+        Some(path) => {
+            fs::write(path, csv).expect("cannot write sweep results");
+            eprintln!("Results written to {}", path);
+        }
+        None => print!("{}", csv),
+    }
+}
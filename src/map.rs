@@ -7,10 +7,12 @@ use crossterm::{
     cursor::MoveTo,
     style::{Print, SetForegroundColor, Color},
 };
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::io::{stdout, Write};
-use crate::robot::Robot;
+use crate::robot::{Robot, RobotType};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Tile {
     Empty,
     Obstacle,
@@ -18,46 +20,148 @@ pub enum Tile {
     Mineral,     // ⛏️ Mineral Resource
     Scientific,  // 🔬 Scientific Point of Interest
     Station,    // 📡 Station
+    LargeMineral,     // ⛰ Mineral deposit that takes two Miners at once
+    LargeScientific,  // 🗿 Scientific site that takes a Miner and a Scientist at once
+}
+
+// Per-tile probability of each resource on a non-obstacle cell
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceDensity {
+    pub energy: f64,
+    pub mineral: f64,
+    pub scientific: f64,
+}
+
+impl ResourceDensity {
+    pub fn uniform(probability: f64) -> Self {
+        Self {
+            energy: probability,
+            mineral: probability,
+            scientific: probability,
+        }
+    }
+}
+
+impl Default for ResourceDensity {
+    fn default() -> Self {
+        Self::uniform(0.03)
+    }
+}
+
+pub fn tile_symbol(tile: Tile) -> (char, Color) {
+    match tile {
+        Tile::Empty => ('.', Color::White),
+        Tile::Obstacle => ('#', Color::DarkGrey),
+        Tile::Energy => ('⚡', Color::Yellow),
+        Tile::Mineral => ('⛏', Color::Green),
+        Tile::Scientific => ('🔬', Color::Cyan),
+        Tile::Station => ('📡', Color::Magenta),
+        Tile::LargeMineral => ('⛰', Color::Green),
+        Tile::LargeScientific => ('🗿', Color::Cyan),
+    }
+}
+
+// Single-width stand-ins for `tile_symbol` on terminals without emoji support
+pub fn tile_ascii(tile: Tile) -> char {
+    match tile {
+        Tile::Empty => '.',
+        Tile::Obstacle => '#',
+        Tile::Energy => '*',
+        Tile::Mineral => 'm',
+        Tile::Scientific => '?',
+        Tile::Station => 'H',
+        Tile::LargeMineral => 'W',
+        Tile::LargeScientific => '!',
+    }
+}
+
+pub fn robot_symbol(robot_type: RobotType) -> char {
+    match robot_type {
+        RobotType::Explorer => 'E',
+        RobotType::Miner => 'M',
+        RobotType::Scientist => 'S',
+    }
 }
 
 pub struct Map {
     pub grid: Vec<Vec<Tile>>,
     pub width: usize,
     pub height: usize,
+    // Cells that cannot be entered for now, such as those under a storm; indexed by `y * width + x`
+    pub blocked: Vec<bool>,
 }
 
 impl Map {
     pub fn new(seed: u32, width: usize, height: usize) -> Self {
+        Self::with_density(seed, width, height, ResourceDensity::default())
+    }
+
+    pub fn with_density(seed: u32, width: usize, height: usize, density: ResourceDensity) -> Self {
         let perlin = Perlin::new(seed);
         let mut rng = StdRng::seed_from_u64(seed as u64);
         let mut grid = vec![vec![Tile::Empty; width]; height];
 
-        grid[5][0] = Tile::Station;
+        // The home station of the first faction, see `faction_home`
+        let station_y = height / 2;
+        grid[station_y][0] = Tile::Station;
 
-        for y in 0..height {
-            for x in 0..width {
-                if x == 0 && y == 5 {
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                if x == 0 && y == station_y {
                     continue;
                 }
 
                 let noise_value = perlin.get([x as f64 / 5.0, y as f64 / 5.0]);
 
                 if noise_value > 0.5 {
-                    grid[y][x] = Tile::Obstacle;
+                    *cell = Tile::Obstacle;
                 } else {
                     let resource_chance: f64 = rng.gen();
-                    if resource_chance < 0.03 {
-                        grid[y][x] = Tile::Energy;
-                    } else if resource_chance < 0.06 {
-                        grid[y][x] = Tile::Mineral;
-                    } else if resource_chance < 0.09 {
-                        grid[y][x] = Tile::Scientific;
+                    if resource_chance < density.energy {
+                        *cell = Tile::Energy;
+                    } else if resource_chance < density.energy + density.mineral {
+                        *cell = Tile::Mineral;
+                    } else if resource_chance < density.energy + density.mineral + density.scientific {
+                        *cell = Tile::Scientific;
                     }
                 }
             }
         }
 
-        Self { grid, width, height }
+        Self { grid, width, height, blocked: vec![false; width * height] }
+    }
+
+    pub fn is_passable(&self, x: usize, y: usize) -> bool {
+        self.grid[y][x] != Tile::Obstacle && !self.blocked[y * self.width + x]
+    }
+
+    // Walking distance from (x, y) to every cell, indexed by `y * width + x`;
+    // obstacles, blocked cells and cells cut off from (x, y) are `usize::MAX`
+    pub fn distances_from(&self, x: usize, y: usize) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.width * self.height];
+        let mut queue = VecDeque::from([(x, y)]);
+        distances[y * self.width + x] = 0;
+
+        while let Some((cx, cy)) = queue.pop_front() {
+            let next = distances[cy * self.width + cx] + 1;
+            let neighbours = [
+                (cx.wrapping_sub(1), cy),
+                (cx + 1, cy),
+                (cx, cy.wrapping_sub(1)),
+                (cx, cy + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if nx >= self.width || ny >= self.height || !self.is_passable(nx, ny) {
+                    continue;
+                }
+                let index = ny * self.width + nx;
+                if distances[index] == usize::MAX {
+                    distances[index] = next;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        distances
     }
 
     pub fn display_map(&self, robots: &[Robot]) {
@@ -66,14 +170,7 @@ impl Map {
 
         for (y, row) in self.grid.iter().enumerate() {
             for (x, &tile) in row.iter().enumerate() {
-                let (symbol, color) = match tile {
-                    Tile::Empty => ('.', Color::White),
-                    Tile::Obstacle => ('#', Color::DarkGrey),
-                    Tile::Energy => ('⚡', Color::Yellow),
-                    Tile::Mineral => ('⛏', Color::Green),
-                    Tile::Scientific => ('🔬', Color::Cyan),
-                    Tile::Station => ('📡', Color::Magenta),
-                };
+                let (symbol, color) = tile_symbol(tile);
 
                 execute!(stdout, MoveTo(x as u16 * 2, y as u16), SetForegroundColor(color), Print(symbol)).unwrap();
             }
@@ -84,11 +181,7 @@ impl Map {
                 continue;
             }
 
-            let symbol = match robot.robot_type {
-                crate::robot::RobotType::Explorer => 'E',
-                crate::robot::RobotType::Miner => 'M',
-                crate::robot::RobotType::Scientist => 'S',
-            };
+            let symbol = robot_symbol(robot.robot_type);
 
             execute!(
                 stdout,
@@ -0,0 +1,260 @@
+use crate::event::{Event, Record};
+use crate::map::Tile;
+use crate::robot::RobotType;
+use crate::simulation::Simulation;
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RobotStats {
+    pub robot: usize,
+    pub robot_type: RobotType,
+    pub distance: usize,
+    pub energy_used: usize,
+    pub idle_ticks: u64,
+    pub resources_harvested: usize,
+    pub resources_delivered: usize,
+    pub first_discovery: Option<u64>,
+    pub failures: usize,
+    pub repairs: usize,
+    pub broke_down: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TickStats {
+    pub tick: u64,
+    pub moving_robots: usize,
+    pub resources_harvested: usize,
+    pub resources_delivered: usize,
+    pub coverage: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub seed: u32,
+    pub ticks: u64,
+    pub coverage: f64,
+    pub first_discovery: Option<u64>,
+    pub resources_delivered: usize,
+    pub robots: Vec<RobotStats>,
+    pub per_tick: Vec<TickStats>,
+}
+
+pub struct Metrics {
+    seed: u32,
+    robots: Vec<RobotStats>,
+    per_tick: Vec<TickStats>,
+    coverage: f64,
+}
+
+impl Metrics {
+    pub fn new(simulation: &Simulation) -> Self {
+
+        let robots = simulation
+            .robots
+            .iter()
+            .map(|robot| RobotStats {
+                robot: robot.id,
+                robot_type: robot.robot_type,
+                distance: 0,
+                energy_used: 0,
+                idle_ticks: 0,
+                resources_harvested: 0,
+                resources_delivered: 0,
+                first_discovery: None,
+                failures: 0,
+                repairs: 0,
+                broke_down: None,
+            })
+            .collect();
+
+        Self {
+            seed: simulation.seed,
+            robots,
+            per_tick: Vec::new(),
+            coverage: coverage(simulation),
+        }
+    }
+
+    // Folds one tick worth of events into the statistics; call after every `Simulation::step`
+    pub fn record(&mut self, simulation: &Simulation, records: &[Record]) {
+        let mut moved = HashSet::new();
+
+        for record in records {
+            match record.event {
+                Event::RobotMoved { robot, .. } => {
+                    moved.insert(robot);
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.distance += 1;
+                    }
+                }
+                Event::ResourceHarvested { robot, .. } => {
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.resources_harvested += 1;
+                        stats.first_discovery.get_or_insert(record.tick);
+                    }
+                }
+                Event::DiscoveryDelivered { robot, .. } => {
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.resources_delivered += 1;
+                    }
+                }
+                Event::ComponentFailed { robot, .. } => {
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.failures += 1;
+                    }
+                }
+                Event::RobotRepaired { robot, .. } => {
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.repairs += 1;
+                    }
+                }
+                Event::RobotBrokeDown { robot, .. } => {
+                    if let Some(stats) = self.robots.get_mut(robot) {
+                        stats.broke_down.get_or_insert(record.tick);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (stats, robot) in self.robots.iter_mut().zip(&simulation.robots) {
+            stats.energy_used = robot.energy_used;
+            if !moved.contains(&robot.id) {
+                stats.idle_ticks += 1;
+            }
+        }
+
+        self.coverage = coverage(simulation);
+        self.per_tick.push(TickStats {
+            tick: simulation.tick,
+            moving_robots: moved.len(),
+            resources_harvested: self.robots.iter().map(|r| r.resources_harvested).sum(),
+            resources_delivered: self.robots.iter().map(|r| r.resources_delivered).sum(),
+            coverage: self.coverage(),
+        });
+    }
+
+    // `coverage` as of the last recorded tick
+    pub fn coverage(&self) -> f64 {
+        self.coverage
+    }
+
+    pub fn robots(&self) -> &[RobotStats] {
+        &self.robots
+    }
+
+    pub fn per_tick(&self) -> &[TickStats] {
+        &self.per_tick
+    }
+
+    pub fn report(&self) -> Report {
+        Report {
+            seed: self.seed,
+            ticks: self.per_tick.last().map(|t| t.tick).unwrap_or(0),
+            coverage: self.coverage(),
+            first_discovery: self.robots.iter().filter_map(|r| r.first_discovery).min(),
+            resources_delivered: self.robots.iter().map(|r| r.resources_delivered).sum(),
+            robots: self.robots.clone(),
+            per_tick: self.per_tick.clone(),
+        }
+    }
+
+    pub fn display_report(&self) {
+        let report = self.report();
+
+        println!("\n📊 Run statistics:");
+        println!("--------------------------");
+        println!(" - Ticks: {}", report.ticks);
+        println!(" - Map coverage: {:.1}%", report.coverage);
+        match report.first_discovery {
+            Some(tick) => println!(" - First discovery: tick {}", tick),
+            None => println!(" - First discovery: none"),
+        }
+        println!(" - Resources delivered: {}", report.resources_delivered);
+
+        println!("\n🤖 Per robot:");
+        println!(
+            "   {:<5} {:<10} {:>8} {:>8} {:>6} {:>9} {:>9} {:>6} {:>6} {:>7} {:>6}",
+            "id", "type", "distance", "energy", "idle", "harvested", "delivered", "first", "faults", "repairs", "broke"
+        );
+        for r in &report.robots {
+            let first = r.first_discovery.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string());
+            let broke = r.broke_down.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string());
+            println!(
+                "   {:<5} {:<10} {:>8} {:>8} {:>6} {:>9} {:>9} {:>6} {:>6} {:>7} {:>6}",
+                r.robot,
+                format!("{:?}", r.robot_type),
+                r.distance,
+                r.energy_used,
+                r.idle_ticks,
+                r.resources_harvested,
+                r.resources_delivered,
+                first,
+                r.failures,
+                r.repairs,
+                broke
+            );
+        }
+    }
+
+    pub fn robots_csv(&self) -> String {
+        let mut csv = String::from(
+            "robot,robot_type,distance,energy_used,idle_ticks,resources_harvested,resources_delivered,first_discovery,\
+             failures,repairs,broke_down\n",
+        );
+        for r in &self.robots {
+            csv.push_str(&format!(
+                "{},{:?},{},{},{},{},{},{},{},{},{}\n",
+                r.robot,
+                r.robot_type,
+                r.distance,
+                r.energy_used,
+                r.idle_ticks,
+                r.resources_harvested,
+                r.resources_delivered,
+                r.first_discovery.map(|t| t.to_string()).unwrap_or_default(),
+                r.failures,
+                r.repairs,
+                r.broke_down.map(|t| t.to_string()).unwrap_or_default()
+            ));
+        }
+        csv
+    }
+
+    pub fn ticks_csv(&self) -> String {
+        let mut csv = String::from("tick,moving_robots,resources_harvested,resources_delivered,coverage\n");
+        for t in &self.per_tick {
+            csv.push_str(&format!(
+                "{},{},{},{},{:.2}\n",
+                t.tick, t.moving_robots, t.resources_harvested, t.resources_delivered, t.coverage
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.report()).expect("report is always serializable")
+    }
+}
+
+// Percentage of non-obstacle cells that at least one robot has stood on
+pub fn coverage(simulation: &Simulation) -> f64 {
+    let mut passable = 0;
+    let mut visited = 0;
+    for (y, row) in simulation.map.grid.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            if tile != Tile::Obstacle {
+                passable += 1;
+                if simulation.heatmaps.visits.get(x, y) > 0 {
+                    visited += 1;
+                }
+            }
+        }
+    }
+
+    if passable == 0 {
+        return 0.0;
+    }
+    visited as f64 * 100.0 / passable as f64
+}
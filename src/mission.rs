@@ -0,0 +1,127 @@
+use crate::map::Tile;
+use crate::metrics::coverage;
+use crate::simulation::Simulation;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    // Have `count` resources of this kind delivered to the stations
+    Deliver { tile: Tile, count: usize },
+    // Have robots stand on this percentage of the non-obstacle cells
+    Explore(f64),
+}
+
+impl Objective {
+    // Current value and target, in the objective's own unit
+    pub fn progress(&self, simulation: &Simulation) -> (f64, f64) {
+        match *self {
+            Objective::Deliver { tile, count } => (simulation.delivered(tile) as f64, count as f64),
+            Objective::Explore(percent) => (coverage(simulation), percent),
+        }
+    }
+
+    pub fn is_met(&self, simulation: &Simulation) -> bool {
+        let (current, target) = self.progress(simulation);
+        current >= target
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Objective::Deliver { tile: Tile::Mineral, count } => write!(f, "collect {} minerals", count),
+            Objective::Deliver { tile: Tile::Scientific, count } => write!(f, "analyse {} scientific sites", count),
+            Objective::Deliver { tile: Tile::Energy, count } => write!(f, "deliver {} energy", count),
+            Objective::Deliver { tile, count } => write!(f, "deliver {} {:?}", count, tile),
+            Objective::Explore(percent) => write!(f, "explore {}% of the map", percent),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissionStatus {
+    InProgress,
+    Accomplished,
+    Failed(String),
+}
+
+// A set of objectives that must all be met, optionally before a tick limit
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mission {
+    pub objectives: Vec<Objective>,
+    pub tick_limit: Option<u64>,
+}
+
+impl Mission {
+    pub fn evaluate(&self, simulation: &Simulation) -> MissionStatus {
+        if self.objectives.iter().all(|o| o.is_met(simulation)) {
+            return MissionStatus::Accomplished;
+        }
+
+        match self.tick_limit {
+            Some(limit) if simulation.tick >= limit => {
+                let missed: Vec<String> = self
+                    .objectives
+                    .iter()
+                    .filter(|o| !o.is_met(simulation))
+                    .map(|o| o.to_string())
+                    .collect();
+                MissionStatus::Failed(format!("tick limit {} reached without: {}", limit, missed.join(", ")))
+            }
+            _ => MissionStatus::InProgress,
+        }
+    }
+
+    pub fn display_progress(&self, simulation: &Simulation) {
+        println!("\n🎯 Mission:");
+        println!("--------------------------");
+        for objective in &self.objectives {
+            let (current, target) = objective.progress(simulation);
+            let mark = if current >= target { "✅" } else { "❌" };
+            println!(" {} {} ({}/{})", mark, objective, current.floor(), target);
+        }
+        if let Some(limit) = self.tick_limit {
+            println!(" - Tick limit: {} (used {})", limit, simulation.tick);
+        }
+    }
+}
+
+// Parses `minerals=5,science=3,energy=2,explore=40,ticks=500`; every key is optional, but at least one objective is needed
+impl std::str::FromStr for Mission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mission = Mission::default();
+
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("mission objective '{}' is not key=value", part))?;
+            let invalid = || format!("invalid value '{}' for mission objective '{}'", value, key);
+            let deliver = |tile| -> Result<Objective, String> {
+                Ok(Objective::Deliver { tile, count: value.parse().map_err(|_| invalid())? })
+            };
+
+            match key {
+                "minerals" => mission.objectives.push(deliver(Tile::Mineral)?),
+                "science" => mission.objectives.push(deliver(Tile::Scientific)?),
+                "energy" => mission.objectives.push(deliver(Tile::Energy)?),
+                "explore" => mission
+                    .objectives
+                    .push(Objective::Explore(value.trim_end_matches('%').parse().map_err(|_| invalid())?)),
+                "ticks" => mission.tick_limit = Some(value.parse().map_err(|_| invalid())?),
+                _ => {
+                    return Err(format!(
+                        "unknown mission objective '{}' (expected minerals, science, energy, explore or ticks)",
+                        key
+                    ))
+                }
+            }
+        }
+
+        if mission.objectives.is_empty() {
+            return Err(format!("mission '{}' has no objectives (expected minerals, science, energy or explore)", s));
+        }
+        Ok(mission)
+    }
+}
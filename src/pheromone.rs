@@ -0,0 +1,60 @@
+use crate::heatmap::Heatmap;
+use crate::robot::Robot;
+
+// How strongly robots mark the ground and how fast the marks fade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stigmergy {
+    // Pheromone a robot leaves on its cell each tick
+    pub deposit: u32,
+    // Fraction of every pheromone that evaporates each tick
+    pub evaporation: f64,
+}
+
+impl Default for Stigmergy {
+    fn default() -> Self {
+        Self {
+            deposit: 100,
+            evaporation: 0.02,
+        }
+    }
+}
+
+// Decaying scent layers over the map grid, sensed by robots with the `ant` strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pheromones {
+    pub stigmergy: Stigmergy,
+    // Laid by robots carrying resources home, so it marks the way between deposits and stations
+    pub trail: Heatmap,
+    // Laid by every other working robot, so ants spread out instead of searching the same ground twice
+    pub explored: Heatmap,
+}
+
+impl Pheromones {
+    pub fn new(stigmergy: Stigmergy, width: usize, height: usize) -> Self {
+        Self {
+            stigmergy,
+            trail: Heatmap::new(width, height),
+            explored: Heatmap::new(width, height),
+        }
+    }
+
+    // Evaporates the old scent and lets every working robot mark its cell
+    pub fn tick(&mut self, robots: &[Robot]) {
+        self.trail.evaporate(self.stigmergy.evaporation);
+        self.explored.evaporate(self.stigmergy.evaporation);
+
+        for robot in robots.iter().filter(|r| !r.is_broken()) {
+            if robot.returning && !robot.discoveries.is_empty() {
+                self.trail.add(robot.x, robot.y, self.stigmergy.deposit);
+            } else {
+                self.explored.add(robot.x, robot.y, self.stigmergy.deposit);
+            }
+        }
+    }
+
+    // How much a foraging ant wants to step onto (x, y): trails pull, explored ground pushes away
+    pub fn attraction(&self, x: usize, y: usize) -> f64 {
+        let deposit = self.stigmergy.deposit.max(1) as f64;
+        (1.0 + self.trail.get(x, y) as f64 / deposit) / (1.0 + self.explored.get(x, y) as f64 / deposit)
+    }
+}
@@ -0,0 +1,220 @@
+use crate::env::{Env, Reward, Rewards};
+use crate::event::Record;
+use crate::map::Tile;
+use crate::robot::{Command, Robot, RobotType};
+use crate::script::SENSE_RANGE;
+use crate::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+// Settings of the episodes a session plays; `reset` can override each of them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Episode {
+    pub seed: u32,
+    pub width: usize,
+    pub height: usize,
+    pub robots: usize,
+    // An episode ends when the swarm is parked or after this many ticks
+    pub max_ticks: u64,
+}
+
+impl Default for Episode {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            width: 20,
+            height: 10,
+            robots: 3,
+            max_ticks: 1000,
+        }
+    }
+}
+
+// What a client sends, one JSON object per line
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    Reset {
+        seed: Option<u32>,
+        width: Option<usize>,
+        height: Option<usize>,
+        robots: Option<usize>,
+        max_ticks: Option<u64>,
+    },
+    // Robots without an action follow the built-in behaviour
+    Step {
+        #[serde(default)]
+        actions: Vec<Action>,
+    },
+    Close,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Action {
+    pub robot: usize,
+    // Same actions as a controller script: north, south, east, west, stay, home or default
+    pub action: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Sensed {
+    pub x: usize,
+    pub y: usize,
+    pub tile: Tile,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RobotObservation {
+    pub id: usize,
+    pub faction: usize,
+    pub robot_type: RobotType,
+    pub x: usize,
+    pub y: usize,
+    pub energy: usize,
+    pub health: u32,
+    pub cargo: usize,
+    pub returning: bool,
+    // Every non-empty tile within `SENSE_RANGE` cells
+    pub sensed: Vec<Sensed>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Observation {
+    pub tick: u64,
+    pub done: bool,
+    // Resources delivered by each faction so far
+    pub scores: Vec<usize>,
+    pub robots: Vec<RobotObservation>,
+    // Everything that happened since the last observation
+    pub events: Vec<Record>,
+}
+
+impl Observation {
+    pub fn new(simulation: &mut Simulation, done: bool) -> Self {
+        Self {
+            tick: simulation.tick,
+            done,
+            scores: (0..simulation.factions).map(|faction| simulation.score(faction)).collect(),
+            robots: simulation.robots.iter().map(|robot| observe(simulation, robot)).collect(),
+            events: simulation.take_events(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    // Answer to `reset`
+    Observation(Observation),
+    // Answer to `step`; `done` is part of the observation
+    Step {
+        #[serde(flatten)]
+        observation: Observation,
+        reward: Reward,
+    },
+    Error { message: String },
+    Closed,
+}
+
+// One client's episodes: a Gym-like reset/step loop around an `Env`
+pub struct Session {
+    pub episode: Episode,
+    env: Option<Env>,
+}
+
+impl Session {
+    pub fn new(episode: Episode) -> Self {
+        Self { episode, env: None }
+    }
+
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Reset { seed, width, height, robots, max_ticks } => {
+                let episode = Episode {
+                    seed: seed.unwrap_or(self.episode.seed),
+                    width: width.unwrap_or(self.episode.width),
+                    height: height.unwrap_or(self.episode.height),
+                    robots: robots.unwrap_or(self.episode.robots),
+                    max_ticks: max_ticks.unwrap_or(self.episode.max_ticks),
+                };
+                self.reset(episode).map_or_else(|message| Response::Error { message }, Response::Observation)
+            }
+            Request::Step { actions } => match self.step(&actions) {
+                Ok((observation, reward)) => Response::Step { observation, reward },
+                Err(message) => Response::Error { message },
+            },
+            Request::Close => Response::Closed,
+        }
+    }
+
+    fn reset(&mut self, episode: Episode) -> Result<Observation, String> {
+        let env = self.env.insert(Env::new(episode, Rewards::default())?);
+        self.episode = episode;
+        Ok(env.observe())
+    }
+
+    fn step(&mut self, actions: &[Action]) -> Result<(Observation, Reward), String> {
+        let Some(env) = &mut self.env else {
+            return Err("no episode running: send reset first".to_string());
+        };
+        if env.is_done() {
+            return Err("the episode is over: send reset".to_string());
+        }
+
+        // Check every action before applying any, so a bad request leaves the episode untouched
+        let commands = actions
+            .iter()
+            .map(|action| Ok((action.robot, action.action.parse::<Command>()?)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let (observation, reward, _) = env.step(&commands)?;
+        Ok((observation, reward))
+    }
+}
+
+// Answers requests read line by line until the client closes the session or the input ends
+pub fn serve<R: BufRead, W: Write>(reader: R, mut writer: W, episode: Episode) -> io::Result<()> {
+    let mut session = Session::new(episode);
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => session.handle(request),
+            Err(e) => Response::Error { message: format!("invalid request: {}", e) },
+        };
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        if matches!(response, Response::Closed) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn observe(simulation: &Simulation, robot: &Robot) -> RobotObservation {
+    let map = &simulation.map;
+    let mut sensed = Vec::new();
+    for y in robot.y.saturating_sub(SENSE_RANGE)..=(robot.y + SENSE_RANGE).min(map.height - 1) {
+        for x in robot.x.saturating_sub(SENSE_RANGE)..=(robot.x + SENSE_RANGE).min(map.width - 1) {
+            if map.grid[y][x] != Tile::Empty {
+                sensed.push(Sensed { x, y, tile: map.grid[y][x] });
+            }
+        }
+    }
+
+    RobotObservation {
+        id: robot.id,
+        faction: robot.faction,
+        robot_type: robot.robot_type,
+        x: robot.x,
+        y: robot.y,
+        energy: robot.energy,
+        health: robot.health,
+        cargo: robot.discoveries.len(),
+        returning: robot.returning,
+        sensed,
+    }
+}
@@ -0,0 +1,243 @@
+use crate::event::Event;
+use crate::robot::Robot;
+use crate::station::Station;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+
+// Radio settings; with a range of 0 the radio is off and stations only learn what robots bring home
+#[derive(Debug, Clone, PartialEq)]
+pub struct Radio {
+    // Straight-line distance in cells a single hop can cover
+    pub range: usize,
+    // Ticks each hop adds to the delivery time
+    pub latency: u64,
+    // Chance per hop that a message is lost
+    pub loss: f64,
+    // Ticks between two telemetry reports from the same robot
+    pub interval: u64,
+    // Fixed relay beacons, usable by every faction
+    pub relays: Vec<(usize, usize)>,
+}
+
+impl Default for Radio {
+    fn default() -> Self {
+        Self {
+            range: 0,
+            latency: 1,
+            loss: 0.0,
+            interval: 5,
+            relays: Vec::new(),
+        }
+    }
+}
+
+impl Radio {
+    pub fn is_enabled(&self) -> bool {
+        self.range > 0
+    }
+
+    fn in_range(&self, (ax, ay): (usize, usize), (bx, by): (usize, usize)) -> bool {
+        let (dx, dy) = (ax.abs_diff(bx), ay.abs_diff(by));
+        dx * dx + dy * dy <= self.range * self.range
+    }
+}
+
+// A robot's report on itself, as the station last heard it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Telemetry {
+    pub robot: usize,
+    pub sent: u64,
+    pub x: usize,
+    pub y: usize,
+    pub energy: usize,
+    pub health: u32,
+    pub cargo: usize,
+}
+
+// The shortest route from a robot to one of its faction's stations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Link {
+    pub station: usize,
+    pub hops: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Traffic {
+    pub sent: usize,
+    pub delivered: usize,
+    pub lost: usize,
+    // Reports that could not be sent because no route home existed
+    pub unreachable: usize,
+    // Sum of the delivery times of every delivered report
+    pub latency: u64,
+}
+
+struct Message {
+    arrival: u64,
+    link: Link,
+    telemetry: Telemetry,
+}
+
+// Routes telemetry from robots to their stations, hopping over other robots of the
+// same faction and relay beacons. Has its own seeded RNG so packet loss does not
+// change how the robots move.
+pub struct Network {
+    pub radio: Radio,
+    pub traffic: Traffic,
+    links: Vec<Option<Link>>,
+    heard: Vec<Option<Telemetry>>,
+    in_flight: Vec<Message>,
+    rng: StdRng,
+}
+
+impl Network {
+    pub fn new(radio: Radio, robots: usize, seed: u32) -> Self {
+        Self {
+            radio,
+            traffic: Traffic::default(),
+            links: vec![None; robots],
+            heard: vec![None; robots],
+            in_flight: Vec::new(),
+            rng: StdRng::seed_from_u64(seed as u64 ^ 0x7ad1_0c0d),
+        }
+    }
+
+    // The robot's route home as of the last tick, `None` when it is out of contact
+    pub fn link(&self, robot: usize) -> Option<Link> {
+        self.links.get(robot).copied().flatten()
+    }
+
+    // The latest report its stations received from the robot
+    pub fn heard(&self, robot: usize) -> Option<&Telemetry> {
+        self.heard.get(robot).and_then(Option::as_ref)
+    }
+
+    pub fn in_contact(&self) -> usize {
+        self.links.iter().filter(|link| link.is_some()).count()
+    }
+
+    pub fn average_latency(&self) -> f64 {
+        if self.traffic.delivered == 0 {
+            return 0.0;
+        }
+        self.traffic.latency as f64 / self.traffic.delivered as f64
+    }
+
+    // Recomputes the routes, sends this tick's reports and delivers the ones that arrive
+    pub fn tick(&mut self, tick: u64, robots: &[Robot], stations: &[Station]) -> Vec<Event> {
+        let mut events = Vec::new();
+        if !self.radio.is_enabled() {
+            return events;
+        }
+        self.links = self.route(robots, stations);
+
+        if tick.is_multiple_of(self.radio.interval.max(1)) {
+            for robot in robots.iter().filter(|r| !r.is_broken()) {
+                self.traffic.sent += 1;
+                let Some(link) = self.links[robot.id] else {
+                    self.traffic.unreachable += 1;
+                    continue;
+                };
+                if self.radio.loss > 0.0 && (0..link.hops).any(|_| self.rng.gen_bool(self.radio.loss)) {
+                    self.traffic.lost += 1;
+                    events.push(Event::MessageLost { robot: robot.id, hops: link.hops });
+                    continue;
+                }
+                self.in_flight.push(Message {
+                    arrival: tick + self.radio.latency * link.hops as u64,
+                    link,
+                    telemetry: Telemetry {
+                        robot: robot.id,
+                        sent: tick,
+                        x: robot.x,
+                        y: robot.y,
+                        energy: robot.energy,
+                        health: robot.health,
+                        cargo: robot.discoveries.len(),
+                    },
+                });
+            }
+        }
+
+        let (arrived, in_flight) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition(|message| message.arrival <= tick);
+        self.in_flight = in_flight;
+        for Message { link, telemetry, .. } in arrived {
+            self.traffic.delivered += 1;
+            self.traffic.latency += tick - telemetry.sent;
+            let heard = &mut self.heard[telemetry.robot];
+            if heard.is_none_or(|last| last.sent < telemetry.sent) {
+                *heard = Some(telemetry);
+            }
+            events.push(Event::TelemetryReceived {
+                robot: telemetry.robot,
+                station: link.station,
+                x: telemetry.x,
+                y: telemetry.y,
+                sent: telemetry.sent,
+                hops: link.hops,
+            });
+        }
+
+        events
+    }
+
+    // Breadth-first search outwards from each faction's stations over relays and working robots
+    fn route(&self, robots: &[Robot], stations: &[Station]) -> Vec<Option<Link>> {
+        let mut links = vec![None; robots.len()];
+        let factions = robots.iter().map(|r| r.faction + 1).max().unwrap_or(0);
+
+        for faction in 0..factions {
+            let mut relays = vec![false; self.radio.relays.len()];
+            let mut queue: VecDeque<((usize, usize), Link)> = stations
+                .iter()
+                .filter(|station| station.faction == faction)
+                .map(|station| ((station.x, station.y), Link { station: station.id, hops: 0 }))
+                .collect();
+
+            while let Some((position, link)) = queue.pop_front() {
+                let next = Link { hops: link.hops + 1, ..link };
+                for (reached, &relay) in relays.iter_mut().zip(&self.radio.relays) {
+                    if !*reached && self.radio.in_range(position, relay) {
+                        *reached = true;
+                        queue.push_back((relay, next));
+                    }
+                }
+                for robot in robots.iter().filter(|r| r.faction == faction && !r.is_broken()) {
+                    if links[robot.id].is_none() && self.radio.in_range(position, (robot.x, robot.y)) {
+                        links[robot.id] = Some(next);
+                        queue.push_back(((robot.x, robot.y), next));
+                    }
+                }
+            }
+        }
+
+        links
+    }
+
+    pub fn display_report(&self, robots: &[Robot]) {
+        if !self.radio.is_enabled() {
+            return;
+        }
+
+        let traffic = &self.traffic;
+        println!("\n📻 Radio:");
+        println!("--------------------------");
+        println!(
+            " - Reports: {} sent, {} delivered, {} lost, {} out of range",
+            traffic.sent, traffic.delivered, traffic.lost, traffic.unreachable
+        );
+        println!(" - Average latency: {:.1} ticks", self.average_latency());
+        for robot in robots {
+            match self.heard(robot.id) {
+                Some(t) => println!(
+                    " - #{} last heard at tick {}: ({}, {}), {} energy, {} hp, carrying {}",
+                    robot.id, t.sent, t.x, t.y, t.energy, t.health, t.cargo
+                ),
+                None => println!(" - #{} never heard from", robot.id),
+            }
+        }
+    }
+}
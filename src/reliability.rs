@@ -0,0 +1,105 @@
+use crate::event::Event;
+use crate::robot::{Fault, Robot, MAX_HEALTH};
+use crate::station::Station;
+use crate::world::World;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Wear, breakdowns and repairs; everything is off by default so robots never lose health
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reliability {
+    // Chance per move of losing one point of health
+    pub wear: f64,
+    // Chance per tick that a working component fails
+    pub failure_rate: f64,
+    // Health lost per tick inside a storm
+    pub hazard_damage: u32,
+    // Health restored per tick while docked at one of the robot's own stations
+    pub repair_rate: u32,
+}
+
+impl Default for Reliability {
+    fn default() -> Self {
+        Self {
+            wear: 0.0,
+            failure_rate: 0.0,
+            hazard_damage: 0,
+            repair_rate: 10,
+        }
+    }
+}
+
+impl Reliability {
+    // Values that make failures common enough to study in a few hundred ticks, used by `--wear-and-tear`
+    pub fn preset() -> Self {
+        Self {
+            wear: 0.3,
+            failure_rate: 0.005,
+            hazard_damage: 5,
+            repair_rate: 10,
+        }
+    }
+}
+
+// Applies the reliability model to the swarm once per tick, with its own seeded RNG
+pub struct Maintenance {
+    pub reliability: Reliability,
+    rng: StdRng,
+}
+
+impl Maintenance {
+    pub fn new(reliability: Reliability, seed: u32) -> Self {
+        Self {
+            reliability,
+            rng: StdRng::seed_from_u64(seed as u64 ^ 0xfa11_edd5),
+        }
+    }
+
+    // `moved[i]` says whether robot `i` changed cell this tick
+    pub fn tick(&mut self, robots: &mut [Robot], moved: &[bool], stations: &[Station], world: &World) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        for (robot, &moved) in robots.iter_mut().zip(moved) {
+            if robot.is_broken() {
+                continue;
+            }
+            let docked = stations
+                .iter()
+                .any(|s| s.faction == robot.faction && s.x == robot.x && s.y == robot.y);
+
+            if docked {
+                // Faulty components are swapped straight away; health comes back gradually
+                let damaged = robot.health < MAX_HEALTH || !robot.faults.is_empty();
+                robot.faults.clear();
+                robot.health = (robot.health + self.reliability.repair_rate).min(MAX_HEALTH);
+                if damaged && robot.health == MAX_HEALTH {
+                    events.push(Event::RobotRepaired { robot: robot.id, x: robot.x, y: robot.y });
+                }
+                continue;
+            }
+
+            let mut damage = 0;
+            if moved && self.reliability.wear > 0.0 && self.rng.gen_bool(self.reliability.wear) {
+                damage += 1;
+            }
+            if world.storm_at(robot.x, robot.y) {
+                damage += self.reliability.hazard_damage;
+            }
+            robot.health = robot.health.saturating_sub(damage);
+
+            if self.reliability.failure_rate > 0.0 && self.rng.gen_bool(self.reliability.failure_rate) {
+                let fault = if self.rng.gen_bool(0.5) { Fault::SensorDegraded } else { Fault::MotorSlowed };
+                if !robot.faults.contains(&fault) {
+                    robot.faults.push(fault);
+                    events.push(Event::ComponentFailed { robot: robot.id, fault });
+                }
+            }
+
+            if robot.is_broken() {
+                events.push(Event::RobotBrokeDown { robot: robot.id, x: robot.x, y: robot.y });
+            }
+        }
+
+        events
+    }
+}
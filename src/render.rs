@@ -0,0 +1,151 @@
+use crate::simulation::Simulation;
+use crate::tui::{compose, Control, Frame, Glyphs, Status, Tui};
+use crate::viewport::Viewport;
+use crossterm::terminal;
+use std::io::{self, Write};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    // Colour and emoji through crossterm
+    Ansi,
+    // Colour through crossterm, single-width ASCII glyphs
+    NoEmoji,
+    // Plain text frames on stdout: no colour, no emoji, no cursor movement
+    Ascii,
+    // Draws nothing
+    Null,
+}
+
+impl std::str::FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" | "color" => Ok(RenderMode::Ansi),
+            "no-emoji" => Ok(RenderMode::NoEmoji),
+            "ascii" | "plain" => Ok(RenderMode::Ascii),
+            "none" | "null" => Ok(RenderMode::Null),
+            _ => Err(format!("unknown render mode '{}' (expected ansi, no-emoji, ascii or none)", s)),
+        }
+    }
+}
+
+pub trait Renderer {
+    fn draw(&mut self, simulation: &Simulation, status: &Status, viewport: &mut Viewport) -> io::Result<()>;
+
+    // Waits up to `timeout` for user input; renderers without input just let the time pass
+    fn poll_control(&mut self, timeout: Duration) -> io::Result<Option<Control>> {
+        sleep(timeout);
+        Ok(None)
+    }
+
+    // Whether `poll_control` can return anything, i.e. someone can pause, seek or quit
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+pub fn create(mode: RenderMode) -> io::Result<Box<dyn Renderer>> {
+    Ok(match mode {
+        RenderMode::Ansi => Box::new(CrosstermRenderer::new(Glyphs::Emoji)?),
+        RenderMode::NoEmoji => Box::new(CrosstermRenderer::new(Glyphs::Ascii)?),
+        RenderMode::Ascii => Box::new(AsciiRenderer::new(io::stdout())),
+        RenderMode::Null => Box::new(NullRenderer),
+    })
+}
+
+pub struct CrosstermRenderer {
+    tui: Tui,
+    glyphs: Glyphs,
+}
+
+impl CrosstermRenderer {
+    pub fn new(glyphs: Glyphs) -> io::Result<Self> {
+        Ok(Self { tui: Tui::enter()?, glyphs })
+    }
+}
+
+impl Renderer for CrosstermRenderer {
+    fn draw(&mut self, simulation: &Simulation, status: &Status, viewport: &mut Viewport) -> io::Result<()> {
+        let size = self.tui.size()?;
+        let frame = compose(simulation, status, viewport, size, self.glyphs);
+        self.tui.draw(&frame)
+    }
+
+    fn poll_control(&mut self, timeout: Duration) -> io::Result<Option<Control>> {
+        self.tui.poll_control(timeout)
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+// Writes every frame as plain lines of text, suitable for CI logs and pipes
+pub struct AsciiRenderer<W: Write> {
+    writer: W,
+    size: (usize, usize),
+}
+
+impl<W: Write> AsciiRenderer<W> {
+    pub fn new(writer: W) -> Self {
+        let size = terminal::size()
+            .map(|(columns, rows)| (columns as usize, rows as usize))
+            .unwrap_or((120, 40));
+        Self::with_size(writer, size)
+    }
+
+    pub fn with_size(writer: W, size: (usize, usize)) -> Self {
+        Self { writer, size }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Renderer for AsciiRenderer<W> {
+    fn draw(&mut self, simulation: &Simulation, status: &Status, viewport: &mut Viewport) -> io::Result<()> {
+        let frame = compose(simulation, status, viewport, self.size, Glyphs::Ascii);
+        let mut lines = frame_lines(&frame);
+        // The bottom row is the key help, which means nothing without a keyboard
+        lines.pop();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+
+        writeln!(self.writer, "--- tick {} ---", simulation.tick)?;
+        for line in lines {
+            writeln!(self.writer, "{}", line)?;
+        }
+        self.writer.flush()
+    }
+}
+
+pub struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn draw(&mut self, _: &Simulation, _: &Status, _: &mut Viewport) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Frame rows as text with trailing blanks removed; blank rows at the bottom are dropped
+pub fn frame_lines(frame: &Frame) -> Vec<String> {
+    let mut lines: Vec<String> = (0..frame.height)
+        .map(|y| {
+            let line: String = (0..frame.width)
+                .map(|x| frame.get(x, y).symbol)
+                .filter(|&c| c != '\0')
+                .collect();
+            line.trim_end().to_string()
+        })
+        .collect();
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
@@ -0,0 +1,108 @@
+use crate::event::{Event, Record};
+use crate::map::Tile;
+use crate::robot::Robot;
+use crate::simulation::{Simulation, SimulationConfig, Swarm};
+use crate::station::Station;
+
+pub struct Replay {
+    records: Vec<Record>,
+    config: SimulationConfig,
+    // The run as of `simulation.tick`, built from the first `applied` records
+    simulation: Simulation,
+    applied: usize,
+}
+
+impl Replay {
+    // Returns None when the log does not start with a RunStarted event
+    pub fn new(records: Vec<Record>) -> Option<Self> {
+        let config = match records.first().map(|r| &r.event) {
+            Some(&Event::RunStarted { seed, width, height, density, cooperation, .. }) => SimulationConfig {
+                // Robots and stations come from the log
+                swarm: Swarm::Fixed { explorers: 0, miners: 0, scientists: 0 },
+                density,
+                cooperation,
+                ..SimulationConfig::new(seed, width, height)
+            },
+            _ => return None,
+        };
+        let simulation = Simulation::from_config(config.clone());
+        Some(Self { records, config, simulation, applied: 0 })
+    }
+
+    pub fn last_tick(&self) -> u64 {
+        self.records.last().map(|r| r.tick).unwrap_or(0)
+    }
+
+    // The map, robots and stations as they were at the end of `tick`. Moving forward only
+    // applies the events since the last call; moving back starts over from the beginning.
+    pub fn seek(&mut self, tick: u64) -> &Simulation {
+        if tick < self.simulation.tick {
+            self.simulation = Simulation::from_config(self.config.clone());
+            self.applied = 0;
+        }
+        while let Some(record) = self.records.get(self.applied).filter(|r| r.tick <= tick) {
+            apply(&mut self.simulation, &record.event);
+            self.applied += 1;
+        }
+        self.simulation.tick = tick;
+        &self.simulation
+    }
+}
+
+fn apply(simulation: &mut Simulation, event: &Event) {
+    let map = &mut simulation.map;
+    let robots = &mut simulation.robots;
+
+    match *event {
+        Event::RobotBuilt { robot, robot_type, x, y, faction } => {
+            let mut built = Robot::build(robot, robot_type, x, y);
+            built.faction = faction;
+            robots.push(built);
+            simulation.factions = simulation.factions.max(faction + 1);
+        }
+        Event::RobotMoved { robot, to: (x, y), .. } => {
+            if let Some(r) = robots.iter_mut().find(|r| r.id == robot) {
+                r.x = x;
+                r.y = y;
+            }
+        }
+        Event::ResourceHarvested { x, y, .. } if map.grid[y][x] != Tile::Station => {
+            map.grid[y][x] = Tile::Empty;
+        }
+        // Credited to the station the robot stands on, or else its faction's first one
+        Event::DiscoveryDelivered { robot, x, y, tile } => {
+            if let Some(r) = robots.iter().find(|r| r.id == robot) {
+                let stations = &mut simulation.stations;
+                let index = stations
+                    .iter()
+                    .position(|s| (s.x, s.y) == (r.x, r.y))
+                    .or_else(|| stations.iter().position(|s| s.faction == r.faction));
+                if let Some(index) = index {
+                    stations[index].deliver((x, y, tile));
+                }
+            }
+        }
+        Event::StationBuilt { station, x, y, faction } => {
+            map.grid[y][x] = Tile::Station;
+            if !simulation.stations.iter().any(|s| (s.x, s.y) == (x, y)) {
+                let mut built = Station::build(station, x, y);
+                built.faction = faction;
+                simulation.stations.push(built);
+                simulation.factions = simulation.factions.max(faction + 1);
+            }
+        }
+        Event::ResourceSpawned { x, y, tile } => map.grid[y][x] = tile,
+        Event::RobotBrokeDown { robot, .. } => {
+            if let Some(r) = robots.iter_mut().find(|r| r.id == robot) {
+                r.health = 0;
+            }
+        }
+        Event::RobotStranded { robot, .. } => {
+            if let Some(r) = robots.iter_mut().find(|r| r.id == robot) {
+                r.returning = true;
+                r.energy = 0;
+            }
+        }
+        _ => {}
+    }
+}
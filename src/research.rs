@@ -0,0 +1,185 @@
+use crate::event::{Event, Record};
+use crate::map::Tile;
+use crate::robot::{Robot, RobotType};
+use crate::simulation::faction_name;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Research settings; with `analysis_ticks` at 0 analysis is instant and nothing is researched
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Research {
+    // Ticks a Scientist dwells on a scientific site before it has a sample
+    pub analysis_ticks: u64,
+    // How far robots spot resources before any sensor upgrade; None keeps the robots' own range,
+    // which the sensor upgrades cannot widen
+    pub sensor_range: Option<usize>,
+}
+
+impl Research {
+    // Used by `--research`
+    pub fn preset() -> Self {
+        Self {
+            analysis_ticks: 5,
+            sensor_range: Some(8),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.analysis_ticks > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Upgrade {
+    // Twice the energy from every energy cell
+    Batteries,
+    // Resources are spotted twice as far away
+    Sensors,
+    // Scientists need half as long per site
+    FastAnalysis,
+    // Three times the energy from every energy cell
+    FusionCells,
+    // Resources are spotted anywhere on the map
+    DeepScanner,
+}
+
+// Every upgrade in the order the station buys them, with its cost and prerequisite
+pub const TECH_TREE: [(Upgrade, usize, Option<Upgrade>); 5] = [
+    (Upgrade::Batteries, 5, None),
+    (Upgrade::Sensors, 5, None),
+    (Upgrade::FastAnalysis, 10, Some(Upgrade::Sensors)),
+    (Upgrade::FusionCells, 15, Some(Upgrade::Batteries)),
+    (Upgrade::DeepScanner, 20, Some(Upgrade::FastAnalysis)),
+];
+
+impl fmt::Display for Upgrade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Upgrade::Batteries => "better batteries",
+            Upgrade::Sensors => "bigger sensors",
+            Upgrade::FastAnalysis => "fast analysis",
+            Upgrade::FusionCells => "fusion cells",
+            Upgrade::DeepScanner => "deep scanner",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// One faction's research account
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Lab {
+    pub points: usize,
+    pub spent: usize,
+    pub unlocked: Vec<Upgrade>,
+}
+
+impl Lab {
+    pub fn available(&self) -> usize {
+        self.points - self.spent
+    }
+
+    // The first upgrade in the tree not yet unlocked whose prerequisite is; it may not be affordable yet
+    pub fn next_upgrade(&self) -> Option<(Upgrade, usize)> {
+        TECH_TREE
+            .iter()
+            .find(|(upgrade, _, requires)| {
+                !self.unlocked.contains(upgrade) && requires.is_none_or(|r| self.unlocked.contains(&r))
+            })
+            .map(|&(upgrade, cost, _)| (upgrade, cost))
+    }
+}
+
+// Turns samples delivered by Scientists into research points and spends them on upgrades
+pub struct Labs {
+    pub research: Research,
+    pub labs: Vec<Lab>,
+    seed: u32,
+}
+
+impl Labs {
+    pub fn new(research: Research, factions: usize, seed: u32) -> Self {
+        Self {
+            research,
+            labs: vec![Lab::default(); factions],
+            seed,
+        }
+    }
+
+    // Each site is worth 1 to 5 points, fixed by the seed and its position
+    pub fn value(&self, x: usize, y: usize) -> usize {
+        let mut hash = (self.seed as u64) ^ ((x as u64) << 32 | y as u64);
+        hash = hash.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        hash ^= hash >> 29;
+        (hash % 5) as usize + 1
+    }
+
+    // Scores this tick's deliveries, then buys every upgrade the factions can afford
+    pub fn tick(&mut self, records: &[Record], robots: &mut [Robot]) -> Vec<Event> {
+        let mut events = Vec::new();
+        if !self.research.is_enabled() {
+            return events;
+        }
+
+        for record in records {
+            if let Event::DiscoveryDelivered { robot, x, y, tile: Tile::Scientific } = record.event {
+                if robots[robot].robot_type == RobotType::Scientist {
+                    let points = self.value(x, y);
+                    self.labs[robots[robot].faction].points += points;
+                    events.push(Event::ResearchDelivered { robot, points });
+                }
+            }
+        }
+
+        for (faction, lab) in self.labs.iter_mut().enumerate() {
+            while let Some((upgrade, cost)) = lab.next_upgrade().filter(|&(_, cost)| lab.available() >= cost) {
+                lab.spent += cost;
+                lab.unlocked.push(upgrade);
+                for robot in robots.iter_mut().filter(|r| r.faction == faction) {
+                    apply(upgrade, robot);
+                }
+                events.push(Event::UpgradeResearched { faction, upgrade });
+            }
+        }
+
+        events
+    }
+
+    pub fn display_report(&self) {
+        if !self.research.is_enabled() {
+            return;
+        }
+
+        println!("\n🧪 Research:");
+        println!("--------------------------");
+        for (faction, lab) in self.labs.iter().enumerate() {
+            let unlocked: Vec<String> = lab.unlocked.iter().map(Upgrade::to_string).collect();
+            println!(
+                " - {}: {} points, {} spent, upgrades: {}",
+                faction_name(faction),
+                lab.points,
+                lab.spent,
+                if unlocked.is_empty() { "none".to_string() } else { unlocked.join(", ") }
+            );
+        }
+    }
+}
+
+// Sets a robot's equipment up for a research-enabled run
+pub fn equip(research: &Research, robot: &mut Robot) {
+    if research.sensor_range.is_some() {
+        robot.equipment.sensor_range = research.sensor_range;
+    }
+    robot.equipment.analysis_ticks = research.analysis_ticks;
+}
+
+fn apply(upgrade: Upgrade, robot: &mut Robot) {
+    let equipment = &mut robot.equipment;
+    match upgrade {
+        Upgrade::Batteries => equipment.battery = 10,
+        Upgrade::FusionCells => equipment.battery = 15,
+        Upgrade::Sensors => equipment.sensor_range = equipment.sensor_range.map(|range| range * 2),
+        Upgrade::DeepScanner => equipment.sensor_range = None,
+        Upgrade::FastAnalysis => equipment.analysis_ticks = equipment.analysis_ticks.div_ceil(2),
+    }
+}
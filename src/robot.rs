@@ -1,28 +1,152 @@
+use rand::distributions::WeightedIndex;
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use crate::event::Event;
 use crate::map::{Map, Tile};
+use crate::pheromone::Pheromones;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum RobotType {
     Explorer,   // 📡 Explore the map
     Miner,      // ⛏️ Extracts minerals
     Scientist,  // 🔬 Analyzes scientific sites
 }
 
+pub const MAX_HEALTH: u32 = 100;
+// Below this a robot heads home for repairs
+const LOW_HEALTH: u32 = 30;
+// How far a robot with a degraded sensor can still spot resources
+const DEGRADED_SENSOR_RANGE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Fault {
+    // Resources are only noticed nearby
+    SensorDegraded,
+    // Moves only every other tick
+    MotorSlowed,
+}
+
+// What the station's task allocation told a robot to harvest, see `auction.rs`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Task {
+    // No allocation: the robot goes for the nearest resource itself
+    #[default]
+    Unmanaged,
+    Assigned(usize, usize),
+    // Every reachable resource went to a robot with a better bid
+    Idle,
+}
+
+// What a robot controller outside the simulation decided for this tick, see `script.rs`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    // One step in the given direction
+    Move(isize, isize),
+    // Wait in place without spending energy
+    Stay,
+    Home,
+    // Fall back to the built-in behaviour
+    Default,
+}
+
+impl std::str::FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "north" => Ok(Command::Move(0, -1)),
+            "south" => Ok(Command::Move(0, 1)),
+            "west" => Ok(Command::Move(-1, 0)),
+            "east" => Ok(Command::Move(1, 0)),
+            "stay" => Ok(Command::Stay),
+            "home" => Ok(Command::Home),
+            "default" => Ok(Command::Default),
+            _ => Err(format!(
+                "unknown action '{}' (expected north, south, east, west, stay, home or default)",
+                s
+            )),
+        }
+    }
+}
+
+// Hardware the station's research can improve, see `research.rs`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Equipment {
+    // Energy gained from each energy cell
+    pub battery: usize,
+    // How far resources can be spotted; `None` sees the whole map
+    pub sensor_range: Option<usize>,
+    // Ticks a Scientist dwells on a scientific site before it has a sample; 0 is instant
+    pub analysis_ticks: u64,
+}
+
+impl Default for Equipment {
+    fn default() -> Self {
+        Self {
+            battery: 5,
+            sensor_range: None,
+            analysis_ticks: 0,
+        }
+    }
+}
+
+// How a robot picks where to go next
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    // Explorers wander, miners and scientists head for their own resource
+    #[default]
+    Standard,
+    // Every robot heads for the nearest resource it can harvest
+    Greedy,
+    // Robots only sense neighbouring cells and follow or avoid pheromones, see `pheromone.rs`
+    Ant,
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Strategy::Standard),
+            "greedy" => Ok(Strategy::Greedy),
+            "ant" => Ok(Strategy::Ant),
+            _ => Err(format!("unknown strategy '{}' (expected standard, greedy or ant)", s)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Robot {
+    pub id: usize,
+    pub faction: usize,
+    pub strategy: Strategy,
     pub x: usize,
     pub y: usize,
     pub robot_type: RobotType,
     pub discoveries: Vec<(usize, usize, Tile)>,
     pub energy: usize,
+    pub energy_used: usize,
     pub returning: bool,
+    pub health: u32,
+    pub faults: Vec<Fault>,
+    // Next cell on the way to a target the simulation picked, such as an exploration frontier
+    pub waypoint: Option<(usize, usize)>,
+    pub task: Task,
+    pub equipment: Equipment,
+    // Ticks spent analysing the scientific site the robot stands on
+    pub analysis: u64,
+    // Set by a robot controller each tick and consumed by the next move
+    pub command: Option<Command>,
+    // Whether a slowed motor skipped the last move
+    stalled: bool,
 }
 
 impl Robot {
-    pub fn new(width: usize, height: usize, rng: &mut StdRng) -> Self {
+    pub fn new(_width: usize, height: usize, rng: &mut StdRng) -> Self {
         let x = 0;
-        let y = 5;
+        let y = height / 2;
 
         let robot_type = match rng.gen_range(0..3) {
             0 => RobotType::Explorer,
@@ -30,16 +154,43 @@ impl Robot {
             _ => RobotType::Scientist,
         };
 
+        Self::build(0, robot_type, x, y)
+    }
+
+    pub fn build(id: usize, robot_type: RobotType, x: usize, y: usize) -> Self {
         Self {
+            id,
+            faction: 0,
+            strategy: Strategy::Standard,
             x,
             y,
             robot_type,
             discoveries: Vec::new(),
             energy: 10,
+            energy_used: 0,
             returning: false,
+            health: MAX_HEALTH,
+            faults: Vec::new(),
+            waypoint: None,
+            task: Task::Unmanaged,
+            equipment: Equipment::default(),
+            analysis: 0,
+            command: None,
+            stalled: false,
         }
     }
 
+    pub fn is_broken(&self) -> bool {
+        self.health == 0
+    }
+
+    // A Scientist part-way through analysing the site it stands on
+    pub fn is_analysing(&self, map: &Map) -> bool {
+        self.robot_type == RobotType::Scientist
+            && self.analysis > 0
+            && map.grid[self.y][self.x] == Tile::Scientific
+    }
+
     pub fn display_info(&self) {
         println!(
             "Robot ({:?}) -> Position: ({}, {})",
@@ -47,10 +198,35 @@ impl Robot {
         );
     }
 
-    pub fn return_to_station(&mut self, station_x: usize, station_y: usize, map: &Map) -> bool {
+    // Steps down the station's distance field (see `Map::distances_from`); only when the
+    // station cannot be reached from here does it fall back to heading straight for it
+    pub fn return_to_station(
+        &mut self,
+        station_x: usize,
+        station_y: usize,
+        home: &[usize],
+        map: &Map,
+        rng: &mut StdRng,
+    ) -> bool {
         if self.x == station_x && self.y == station_y {
             return true;
         }
+
+        let distance = |x: usize, y: usize| home.get(y * map.width + x).copied().unwrap_or(usize::MAX);
+        let closer = [
+            (self.x.wrapping_sub(1), self.y),
+            (self.x + 1, self.y),
+            (self.x, self.y.wrapping_sub(1)),
+            (self.x, self.y + 1),
+        ]
+        .into_iter()
+        .filter(|&(x, y)| x < map.width && y < map.height && map.is_passable(x, y))
+        .min_by_key(|&(x, y)| distance(x, y))
+        .filter(|&(x, y)| distance(x, y) < distance(self.x, self.y));
+        if let Some((x, y)) = closer {
+            (self.x, self.y) = (x, y);
+            return false;
+        }
     
         let dx = (station_x as isize - self.x as isize).signum();
         let dy = (station_y as isize - self.y as isize).signum();
@@ -58,21 +234,20 @@ impl Robot {
         let new_x = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
         let new_y = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
     
-        if map.grid[new_y][new_x] != Tile::Obstacle {
+        if map.is_passable(new_x, new_y) {
             self.x = new_x;
             self.y = new_y;
             return false;
         }
     
         let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)];
-        let mut rng = rand::thread_rng();
     
         for _ in 0..4 {
             let (dx, dy) = directions[rng.gen_range(0..4)];
             let nx = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
             let ny = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
     
-            if map.grid[ny][nx] != Tile::Obstacle {
+            if map.is_passable(nx, ny) {
                 self.x = nx;
                 self.y = ny;
                 break;
@@ -84,7 +259,11 @@ impl Robot {
 
     fn find_nearest(&self, map: &Map, target: Tile) -> Option<(usize, usize)> {
         let mut nearest = None;
-        let mut min_dist = usize::MAX;
+        let mut range = self.equipment.sensor_range.unwrap_or(usize::MAX - 1);
+        if self.faults.contains(&Fault::SensorDegraded) {
+            range = range.min(DEGRADED_SENSOR_RANGE);
+        }
+        let mut min_dist = range + 1;
 
         for (y, row) in map.grid.iter().enumerate() {
             for (x, &tile) in row.iter().enumerate() {
@@ -100,17 +279,125 @@ impl Robot {
         nearest
     }
 
+    // Tiles `perform_action` picks up for this robot type
+    fn harvestable(&self) -> &'static [Tile] {
+        match self.robot_type {
+            RobotType::Miner => &[Tile::Mineral, Tile::Energy],
+            RobotType::Scientist => &[Tile::Scientific, Tile::Energy],
+            RobotType::Explorer => &[Tile::Energy, Tile::Mineral, Tile::Scientific],
+        }
+    }
+
+    // The closest tile `perform_action` would pick up for this robot type
+    fn find_nearest_harvestable(&self, map: &Map) -> Option<(usize, usize)> {
+        self.harvestable()
+            .iter()
+            .filter_map(|&tile| self.find_nearest(map, tile))
+            .min_by_key(|&(x, y)| self.x.abs_diff(x) + self.y.abs_diff(y))
+    }
+
+    // One greedy step towards `target`; false when an obstacle is in the way
+    fn step_towards(&mut self, (target_x, target_y): (usize, usize), map: &Map) -> bool {
+        let dx = (target_x as isize - self.x as isize).signum();
+        let dy = (target_y as isize - self.y as isize).signum();
+
+        let new_x = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
+        let new_y = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
+
+        if map.is_passable(new_x, new_y) {
+            self.x = new_x;
+            self.y = new_y;
+            return true;
+        }
+        false
+    }
+
+    // Only the four neighbouring cells are sensed: a resource next door is taken straight
+    // away, otherwise the next cell is drawn at random weighted by its pheromones
+    fn forage(&mut self, map: &Map, pheromones: &Pheromones, rng: &mut StdRng) {
+        let neighbours: Vec<(usize, usize)> = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .iter()
+            .filter_map(|&(dx, dy)| {
+                let x = self.x.checked_add_signed(dx).filter(|&x| x < map.width)?;
+                let y = self.y.checked_add_signed(dy).filter(|&y| y < map.height)?;
+                (map.is_passable(x, y)).then_some((x, y))
+            })
+            .collect();
+
+        if let Some(&(x, y)) = neighbours.iter().find(|&&(x, y)| self.harvestable().contains(&map.grid[y][x])) {
+            (self.x, self.y) = (x, y);
+            return;
+        }
+        let weights = neighbours.iter().map(|&(x, y)| pheromones.attraction(x, y));
+        if let Ok(choice) = WeightedIndex::new(weights) {
+            (self.x, self.y) = neighbours[rng.sample(choice)];
+        }
+    }
+
     pub fn move_robot(&mut self, map: &Map, rng: &mut StdRng, station_x: usize, station_y: usize) {
-        if self.energy == 0 {
+        let home = map.distances_from(station_x, station_y);
+        self.navigate(map, None, &home, rng, station_x, station_y);
+    }
+
+    // `move_robot` with access to the pheromone layer; ants without one fall back to the standard behaviour
+    pub fn navigate(
+        &mut self,
+        map: &Map,
+        pheromones: Option<&Pheromones>,
+        home: &[usize],
+        rng: &mut StdRng,
+        station_x: usize,
+        station_y: usize,
+    ) {
+        if self.is_broken() {
+            return;
+        }
+        if self.faults.contains(&Fault::MotorSlowed) {
+            self.stalled = !self.stalled;
+            if self.stalled {
+                return;
+            }
+        }
+        // Stay docked until fully repaired
+        if self.health < MAX_HEALTH && self.x == station_x && self.y == station_y {
+            return;
+        }
+
+        if self.energy == 0 || self.health <= LOW_HEALTH || !self.faults.is_empty() {
             self.returning = true;
         }
 
+        match self.command.take() {
+            Some(Command::Home) => self.returning = true,
+            Some(Command::Stay) if !self.returning => return,
+            Some(Command::Move(dx, dy)) if !self.returning => {
+                self.energy -= 1;
+                self.energy_used += 1;
+                if let (Some(x), Some(y)) = (self.x.checked_add_signed(dx), self.y.checked_add_signed(dy)) {
+                    if x < map.width && y < map.height && map.is_passable(x, y) {
+                        self.x = x;
+                        self.y = y;
+                        self.analysis = 0;
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        // Scientists stay put while analysing; waiting costs no energy
+        if self.is_analysing(map) && !self.returning {
+            return;
+        }
+        self.analysis = 0;
+
         if self.returning {
-            self.returning = !self.return_to_station(station_x, station_y, map);
+            self.returning = !self.return_to_station(station_x, station_y, home, map, rng);
             return;
         }
     
         self.energy -= 1;
+        self.energy_used += 1;
     
         let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)];
     
@@ -125,13 +412,32 @@ impl Robot {
             }
         };
     
-        if self.robot_type == RobotType::Explorer && self.energy > 5 {
+        if let (Strategy::Ant, Some(pheromones)) = (self.strategy, pheromones) {
+            self.forage(map, pheromones, rng);
+            return;
+        }
+
+        if self.strategy == Strategy::Greedy && self.energy > 5 {
+            if let Some(target) = self.find_nearest_harvestable(map) {
+                if self.step_towards(target, map) {
+                    return;
+                }
+            }
+        }
+
+        if self.robot_type == RobotType::Explorer && self.strategy == Strategy::Standard && self.energy > 5 {
+            if let Some((x, y)) = self.waypoint.take() {
+                if self.x.abs_diff(x) + self.y.abs_diff(y) == 1 && map.is_passable(x, y) {
+                    (self.x, self.y) = (x, y);
+                    return;
+                }
+            }
             for _ in 0..4 {
                 let (dx, dy) = directions[rng.gen_range(0..4)];
                 let new_x = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
                 let new_y = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
     
-                if map.grid[new_y][new_x] != Tile::Obstacle {
+                if map.is_passable(new_x, new_y) {
                     self.x = new_x;
                     self.y = new_y;
                     return;
@@ -139,14 +445,20 @@ impl Robot {
             }
         }
     
-        if let Some((target_x, target_y)) = self.find_nearest(map, target_tile) {
+        // Energy is never auctioned, so a robot running low always looks for it itself
+        let target = match self.task {
+            Task::Assigned(x, y) if target_tile != Tile::Energy => Some((x, y)),
+            Task::Idle if target_tile != Tile::Energy => None,
+            _ => self.find_nearest(map, target_tile),
+        };
+        if let Some((target_x, target_y)) = target {
             let dx = (target_x as isize - self.x as isize).signum();
             let dy = (target_y as isize - self.y as isize).signum();
     
             let new_x = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
             let new_y = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
     
-            if map.grid[new_y][new_x] != Tile::Obstacle {
+            if map.is_passable(new_x, new_y) {
                 self.x = new_x;
                 self.y = new_y;
                 return;
@@ -158,7 +470,7 @@ impl Robot {
             let new_x = (self.x as isize + dx).max(0).min((map.width - 1) as isize) as usize;
             let new_y = (self.y as isize + dy).max(0).min((map.height - 1) as isize) as usize;
     
-            if map.grid[new_y][new_x] != Tile::Obstacle {
+            if map.is_passable(new_x, new_y) {
                 self.x = new_x;
                 self.y = new_y;
                 break;
@@ -167,12 +479,12 @@ impl Robot {
     }
 
     pub fn perform_action(&mut self, map: &mut Map) {
-        if self.returning {
+        if self.returning || self.is_broken() {
             return;
         }
         match map.grid[self.y][self.x] {
             Tile::Energy => {
-                self.energy += 5;
+                self.energy += self.equipment.battery;
                 self.discoveries.push((self.x, self.y, Tile::Energy));
                 if map.grid[self.y][self.x] != Tile::Station {
                     map.grid[self.y][self.x] = Tile::Empty;
@@ -185,6 +497,11 @@ impl Robot {
                 }
             }
             Tile::Scientific if self.robot_type == RobotType::Scientist => {
+                self.analysis += 1;
+                if self.analysis < self.equipment.analysis_ticks {
+                    return;
+                }
+                self.analysis = 0;
                 self.discoveries.push((self.x, self.y, Tile::Scientific));
                 if map.grid[self.y][self.x] != Tile::Station {
                     map.grid[self.y][self.x] = Tile::Empty;
@@ -193,7 +510,9 @@ impl Robot {
             tile if self.robot_type == RobotType::Explorer
                 && tile != Tile::Empty
                 && tile != Tile::Obstacle
-                && tile != Tile::Station => {
+                && tile != Tile::Station
+                && tile != Tile::LargeMineral
+                && tile != Tile::LargeScientific => {
                 self.discoveries.push((self.x, self.y, tile));
                 if map.grid[self.y][self.x] != Tile::Station {
                     map.grid[self.y][self.x] = Tile::Empty;
@@ -206,22 +525,45 @@ impl Robot {
     pub fn tick(
         &mut self,
         map: &mut Map,
+        pheromones: &Pheromones,
         station: &mut crate::station::Station,
+        home: &[usize],
         rng: &mut StdRng,
-        station_x: usize,
-        station_y: usize,
-    ) {
-        self.move_robot(map, rng, station_x, station_y);
+    ) -> Vec<Event> {
+        let (station_x, station_y) = (station.x, station.y);
+        let mut events = Vec::new();
+        let from = (self.x, self.y);
+        let was_returning = self.returning;
+
+        self.navigate(map, Some(pheromones), home, rng, station_x, station_y);
+
+        if (self.x, self.y) != from {
+            events.push(Event::RobotMoved { robot: self.id, from, to: (self.x, self.y) });
+        }
+        if self.returning && !was_returning && self.energy == 0 {
+            events.push(Event::RobotStranded { robot: self.id, x: self.x, y: self.y });
+        }
+
+        let harvested = self.discoveries.len();
         self.perform_action(map);
+
+        for &(x, y, tile) in &self.discoveries[harvested..] {
+            events.push(Event::ResourceHarvested { robot: self.id, x, y, tile });
+        }
         
         if self.x == station_x && self.y == station_y && self.returning {
+            for &(x, y, tile) in &self.discoveries {
+                events.push(Event::DiscoveryDelivered { robot: self.id, x, y, tile });
+            }
             station.collect_discoveries(self);
         }
+
+        events
     }
     
 }
 
-pub fn initialize_robots(count: usize, width: usize, height: usize, seed: u32) -> Vec<Robot> {
+pub fn initialize_robots(count: usize, _width: usize, height: usize, seed: u32) -> Vec<Robot> {
     let mut rng = StdRng::seed_from_u64(seed as u64);
     
     let start_x = 0;
@@ -234,13 +576,20 @@ pub fn initialize_robots(count: usize, width: usize, height: usize, seed: u32) -
             _ => RobotType::Scientist,
         };
 
-        Robot {
-            x: start_x,
-            y: start_y,
-            robot_type,
-            discoveries: Vec::new(),
-            energy: 10,
-            returning: false,
-        }
+        Robot::build(i, robot_type, start_x, start_y)
     }).collect()
 }
+
+pub fn initialize_swarm(explorers: usize, miners: usize, scientists: usize, _width: usize, height: usize) -> Vec<Robot> {
+    let start_x = 0;
+    let start_y = height / 2;
+
+    let types = std::iter::repeat_n(RobotType::Explorer, explorers)
+        .chain(std::iter::repeat_n(RobotType::Miner, miners))
+        .chain(std::iter::repeat_n(RobotType::Scientist, scientists));
+
+    types
+        .enumerate()
+        .map(|(i, robot_type)| Robot::build(i, robot_type, start_x, start_y))
+        .collect()
+}
@@ -0,0 +1,204 @@
+use crate::map::{Map, Tile};
+use crate::radio::Network;
+use crate::robot::{Command, Robot, RobotType};
+use rhai::{Array, Dynamic, Engine, Scope, AST};
+
+// How far around itself a scripted robot is told about tiles
+pub const SENSE_RANGE: usize = 3;
+// Stops a runaway script after this many operations per decision
+const MAX_OPERATIONS: u64 = 100_000;
+
+pub fn tile_name(tile: Tile) -> &'static str {
+    match tile {
+        Tile::Empty => "empty",
+        Tile::Obstacle => "obstacle",
+        Tile::Energy => "energy",
+        Tile::Mineral => "mineral",
+        Tile::Scientific => "scientific",
+        Tile::Station => "station",
+        Tile::LargeMineral => "large_mineral",
+        Tile::LargeScientific => "large_scientific",
+    }
+}
+
+pub fn parse_robot_type(name: &str) -> Result<RobotType, String> {
+    match name {
+        "explorer" => Ok(RobotType::Explorer),
+        "miner" => Ok(RobotType::Miner),
+        "scientist" => Ok(RobotType::Scientist),
+        _ => Err(format!("unknown robot type '{}' (expected explorer, miner or scientist)", name)),
+    }
+}
+
+// Rhai scripts that replace the built-in decision of each robot type. A script defines
+// `fn decide(robot)` and returns a direction, "stay", "home" or "default", or a map
+// `#{ action: ..., say: "..." }` to also broadcast a message to its faction.
+pub struct ScriptController {
+    engine: Engine,
+    // Indexed like `type_index`; `None` leaves that type to the built-in behaviour
+    scripts: [Option<AST>; 3],
+    // Messages each robot will read on its next decision
+    inbox: Vec<Vec<String>>,
+    pub errors: usize,
+    pub last_error: Option<String>,
+}
+
+impl ScriptController {
+    // `sources` pairs a robot type with its script; a script without a type applies to every type not given one
+    pub fn new(sources: &[(Option<RobotType>, String)], robots: usize) -> Result<Self, String> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let mut scripts: [Option<AST>; 3] = [None, None, None];
+        let mut fallback = None;
+        for (robot_type, source) in sources {
+            let ast = engine.compile(source).map_err(|e| format!("script error: {}", e))?;
+            if !ast.iter_functions().any(|f| f.name == "decide" && f.params.len() == 1) {
+                return Err("script error: no `fn decide(robot)` defined".to_string());
+            }
+            match robot_type {
+                Some(robot_type) => scripts[type_index(*robot_type)] = Some(ast),
+                None => fallback = Some(ast),
+            }
+        }
+        for script in scripts.iter_mut().filter(|s| s.is_none()) {
+            script.clone_from(&fallback);
+        }
+
+        Ok(Self {
+            engine,
+            scripts,
+            inbox: vec![Vec::new(); robots],
+            errors: 0,
+            last_error: None,
+        })
+    }
+
+    pub fn controls(&self, robot_type: RobotType) -> bool {
+        self.scripts[type_index(robot_type)].is_some()
+    }
+
+    // Runs every scripted robot's `decide` and stores the resulting command on the robot.
+    // Messages said this tick reach the rest of the faction on the next one; with the
+    // radio on, only robots that are both in contact with the network hear each other.
+    pub fn tick(&mut self, tick: u64, map: &Map, robots: &mut [Robot], network: &Network) {
+        let mut said: Vec<(usize, String)> = Vec::new();
+
+        for robot in robots.iter_mut() {
+            let messages = std::mem::take(&mut self.inbox[robot.id]);
+            let Some(ast) = &self.scripts[type_index(robot.robot_type)] else {
+                continue;
+            };
+            if robot.is_broken() {
+                continue;
+            }
+
+            let view = view(tick, map, robot, messages);
+            let result = self.engine.call_fn::<Dynamic>(&mut Scope::new(), ast, "decide", (view,));
+            match result.map_err(|e| e.to_string()).and_then(parse_decision) {
+                Ok((command, message)) => {
+                    robot.command = Some(command);
+                    if let Some(message) = message {
+                        said.push((robot.id, message));
+                    }
+                }
+                Err(error) => {
+                    self.errors += 1;
+                    self.last_error = Some(format!("robot #{} at tick {}: {}", robot.id, tick, error));
+                    robot.command = Some(Command::Default);
+                }
+            }
+        }
+
+        let radio = network.radio.is_enabled();
+        for (sender, message) in said {
+            let faction = robots[sender].faction;
+            if radio && network.link(sender).is_none() {
+                continue;
+            }
+            for robot in robots.iter().filter(|r| r.faction == faction && r.id != sender) {
+                if !radio || network.link(robot.id).is_some() {
+                    self.inbox[robot.id].push(format!("#{}: {}", sender, message));
+                }
+            }
+        }
+    }
+
+    pub fn display_report(&self) {
+        println!("\n📜 Scripts:");
+        println!("--------------------------");
+        println!(" - Runtime errors: {}", self.errors);
+        if let Some(error) = &self.last_error {
+            println!(" - Last error: {}", error);
+        }
+    }
+}
+
+fn type_index(robot_type: RobotType) -> usize {
+    match robot_type {
+        RobotType::Explorer => 0,
+        RobotType::Miner => 1,
+        RobotType::Scientist => 2,
+    }
+}
+
+// The read-only state handed to `decide`
+fn view(tick: u64, map: &Map, robot: &Robot, messages: Vec<String>) -> rhai::Map {
+    let mut sensed = Array::new();
+    for y in robot.y.saturating_sub(SENSE_RANGE)..=(robot.y + SENSE_RANGE).min(map.height - 1) {
+        for x in robot.x.saturating_sub(SENSE_RANGE)..=(robot.x + SENSE_RANGE).min(map.width - 1) {
+            let tile = map.grid[y][x];
+            if tile != Tile::Empty {
+                let mut cell = rhai::Map::new();
+                cell.insert("x".into(), (x as i64).into());
+                cell.insert("y".into(), (y as i64).into());
+                cell.insert("tile".into(), tile_name(tile).into());
+                sensed.push(cell.into());
+            }
+        }
+    }
+
+    let robot_type = match robot.robot_type {
+        RobotType::Explorer => "explorer",
+        RobotType::Miner => "miner",
+        RobotType::Scientist => "scientist",
+    };
+    let mut view = rhai::Map::new();
+    view.insert("tick".into(), (tick as i64).into());
+    view.insert("id".into(), (robot.id as i64).into());
+    view.insert("faction".into(), (robot.faction as i64).into());
+    view.insert("type".into(), robot_type.into());
+    view.insert("x".into(), (robot.x as i64).into());
+    view.insert("y".into(), (robot.y as i64).into());
+    view.insert("width".into(), (map.width as i64).into());
+    view.insert("height".into(), (map.height as i64).into());
+    view.insert("energy".into(), (robot.energy as i64).into());
+    view.insert("health".into(), (robot.health as i64).into());
+    view.insert("cargo".into(), (robot.discoveries.len() as i64).into());
+    view.insert("returning".into(), robot.returning.into());
+    view.insert("here".into(), tile_name(map.grid[robot.y][robot.x]).into());
+    view.insert("sensed".into(), sensed.into());
+    view.insert(
+        "messages".into(),
+        messages.into_iter().map(Dynamic::from).collect::<Array>().into(),
+    );
+    view
+}
+
+fn parse_decision(result: Dynamic) -> Result<(Command, Option<String>), String> {
+    if result.is_unit() {
+        return Ok((Command::Default, None));
+    }
+    if let Some(action) = result.clone().try_cast::<rhai::ImmutableString>() {
+        return Ok((action.parse()?, None));
+    }
+    if let Some(map) = result.try_cast::<rhai::Map>() {
+        let action = match map.get("action") {
+            Some(action) => action.to_string().parse()?,
+            None => Command::Default,
+        };
+        let message = map.get("say").map(|say| say.to_string());
+        return Ok((action, message));
+    }
+    Err("decide must return a string, a map or nothing".to_string())
+}
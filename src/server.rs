@@ -0,0 +1,326 @@
+use crate::event::Record;
+use crate::map::Tile;
+use crate::robot::RobotType;
+use crate::simulation::Simulation;
+use serde::Serialize;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+const DASHBOARD: &str = include_str!("dashboard.html");
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RobotState {
+    pub id: usize,
+    pub faction: usize,
+    pub robot_type: RobotType,
+    pub x: usize,
+    pub y: usize,
+    pub energy: usize,
+    pub health: u32,
+    pub cargo: usize,
+    pub returning: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StationState {
+    pub id: usize,
+    pub faction: usize,
+    pub x: usize,
+    pub y: usize,
+    pub energy: usize,
+    pub minerals: usize,
+    pub science: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cell {
+    pub x: usize,
+    pub y: usize,
+    pub tile: Tile,
+}
+
+// Everything a dashboard needs to draw the run from scratch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub width: usize,
+    pub height: usize,
+    pub map: Vec<Vec<Tile>>,
+    pub robots: Vec<RobotState>,
+    pub stations: Vec<StationState>,
+}
+
+impl Snapshot {
+    pub fn of(simulation: &Simulation) -> Self {
+        Self {
+            tick: simulation.tick,
+            width: simulation.map.width,
+            height: simulation.map.height,
+            map: simulation.map.grid.clone(),
+            robots: robot_states(simulation),
+            stations: station_states(simulation),
+        }
+    }
+}
+
+// What changed during one tick: only the tiles that changed, but every robot and station
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Delta {
+    pub tick: u64,
+    pub cells: Vec<Cell>,
+    pub robots: Vec<RobotState>,
+    pub stations: Vec<StationState>,
+    pub events: Vec<Record>,
+}
+
+impl Delta {
+    pub fn between(previous: &[Vec<Tile>], simulation: &Simulation, events: &[Record]) -> Self {
+        let mut cells = Vec::new();
+        for (y, row) in simulation.map.grid.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                if previous.get(y).and_then(|r| r.get(x)) != Some(&tile) {
+                    cells.push(Cell { x, y, tile });
+                }
+            }
+        }
+
+        Self {
+            tick: simulation.tick,
+            cells,
+            robots: robot_states(simulation),
+            stations: station_states(simulation),
+            events: events.to_vec(),
+        }
+    }
+}
+
+// One WebSocket message
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Update<'a> {
+    Snapshot(&'a Snapshot),
+    Delta(&'a Delta),
+}
+
+// Updates a watcher may have queued before it counts as fallen behind and is dropped
+const QUEUE: usize = 64;
+// How long a watcher's thread waits for a frame before sending queued updates
+const POLL: Duration = Duration::from_millis(20);
+// A client that sends no request, or takes no writes, for this long is dropped
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+// Longest request line or header accepted
+const MAX_REQUEST: u64 = 8 * 1024;
+
+struct Shared {
+    snapshot: Snapshot,
+    // One queue per watcher, drained by that watcher's own thread
+    clients: Vec<SyncSender<String>>,
+}
+
+// Serves the live run on a local address: `GET /state` returns a snapshot, `/ws` streams
+// a snapshot followed by one delta per tick, and `/` is a small dashboard using both
+pub struct Server {
+    pub address: SocketAddr,
+    shared: Arc<Mutex<Shared>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Server {
+    pub fn start(address: &str, simulation: &Simulation) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let shared = Arc::new(Mutex::new(Shared {
+            snapshot: Snapshot::of(simulation),
+            clients: Vec::new(),
+        }));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let (state, stop) = (Arc::clone(&shared), Arc::clone(&stopped));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let state = Arc::clone(&state);
+                thread::spawn(move || {
+                    if let Err(e) = handle(stream, &state) {
+                        eprintln!("observation server: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { address, shared, stopped })
+    }
+
+    // Queues this tick's delta for every watcher, dropping the ones that disconnected or
+    // still have `QUEUE` updates waiting; never waits for the network
+    pub fn publish(&mut self, simulation: &Simulation, events: &[Record]) {
+        let mut shared = self.shared.lock().unwrap();
+        let delta = Delta::between(&shared.snapshot.map, simulation, events);
+        shared.snapshot = Snapshot::of(simulation);
+        if shared.clients.is_empty() {
+            return;
+        }
+
+        let text = serde_json::to_string(&Update::Delta(&delta)).expect("cannot serialise delta");
+        shared.clients.retain(|client| client.try_send(text.clone()).is_ok());
+    }
+
+    pub fn watchers(&self) -> usize {
+        self.shared.lock().unwrap().clients.len()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        // Wakes the accept loop so it sees the flag; dropping the queues ends every watcher
+        self.stopped.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.address);
+        self.shared.lock().unwrap().clients.clear();
+    }
+}
+
+fn handle(stream: TcpStream, shared: &Mutex<Shared>) -> io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    let (path, key) = read_request(&stream)?;
+
+    let (status, content_type, body) = match (path.as_str(), key) {
+        ("/ws", Some(key)) => return watch(stream, &key, shared),
+        ("/state", _) => {
+            let text = serde_json::to_string(&shared.lock().unwrap().snapshot).expect("cannot serialise snapshot");
+            ("200 OK", "application/json", text)
+        }
+        ("/", _) => ("200 OK", "text/html", DASHBOARD.to_string()),
+        _ => ("404 Not Found", "text/plain", String::new()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// The path of the request and its WebSocket key, if it asks for an upgrade
+fn read_request(stream: &TcpStream) -> io::Result<(String, Option<String>)> {
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST));
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let path = line.split_whitespace().nth(1).unwrap_or_default().to_string();
+
+    let mut key = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Sec-WebSocket-Key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+    Ok((path, key))
+}
+
+// Upgrades the connection, registers the watcher's queue and sends the snapshot, then
+// keeps the socket on this thread: answering pings, noticing closes, sending updates
+fn watch(mut stream: TcpStream, key: &str, shared: &Mutex<Shared>) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    stream.set_read_timeout(Some(POLL))?;
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    // Deltas published after the snapshot was taken wait in the queue until it is sent
+    let (sender, updates) = sync_channel(QUEUE);
+    let text = {
+        let mut shared = shared.lock().unwrap();
+        shared.clients.push(sender);
+        serde_json::to_string(&Update::Snapshot(&shared.snapshot)).expect("cannot serialise snapshot")
+    };
+    if socket.send(Message::Text(text)).is_err() {
+        return Ok(());
+    }
+
+    loop {
+        match socket.read() {
+            // Pings are answered and closes acknowledged by the next flush
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return Ok(()),
+        }
+        loop {
+            match updates.try_recv() {
+                Ok(text) => {
+                    if socket.write(Message::Text(text)).is_err() {
+                        return Ok(());
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                // Fell behind, or the run is over
+                Err(TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    let _ = socket.flush();
+                    return Ok(());
+                }
+            }
+        }
+        if socket.flush().is_err() {
+            return Ok(());
+        }
+    }
+}
+
+fn robot_states(simulation: &Simulation) -> Vec<RobotState> {
+    simulation
+        .robots
+        .iter()
+        .map(|robot| RobotState {
+            id: robot.id,
+            faction: robot.faction,
+            robot_type: robot.robot_type,
+            x: robot.x,
+            y: robot.y,
+            energy: robot.energy,
+            health: robot.health,
+            cargo: robot.discoveries.len(),
+            returning: robot.returning,
+        })
+        .collect()
+}
+
+fn station_states(simulation: &Simulation) -> Vec<StationState> {
+    simulation
+        .stations
+        .iter()
+        .map(|station| StationState {
+            id: station.id,
+            faction: station.faction,
+            x: station.x,
+            y: station.y,
+            energy: station.count(Tile::Energy),
+            minerals: station.count(Tile::Mineral),
+            science: station.count(Tile::Scientific),
+        })
+        .collect()
+}
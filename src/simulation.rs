@@ -0,0 +1,475 @@
+use crate::auction::{Allocation, Auctioneer};
+use crate::cooperation::{place_sites, Cooperation, Crews};
+use crate::event::{Event, Record};
+use crate::frontier::{Exploration, Frontiers};
+use crate::heatmap::Heatmaps;
+use crate::map::{Map, ResourceDensity, Tile};
+use crate::mission::{Mission, MissionStatus};
+use crate::robot::{initialize_robots, initialize_swarm, Robot, Strategy};
+use crate::station::{Expansion, Inventory, Station};
+use crate::pheromone::{Pheromones, Stigmergy};
+use crate::radio::{Network, Radio};
+use crate::reliability::{Maintenance, Reliability};
+use crate::research::{equip, Labs, Research};
+use crate::script::ScriptController;
+use crate::world::{Dynamics, World};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Swarm {
+    // `n` robots with randomly drawn types
+    Random(usize),
+    Fixed {
+        explorers: usize,
+        miners: usize,
+        scientists: usize,
+    },
+}
+
+// A rival swarm competing with the first one for the same resources
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Faction {
+    pub swarm: Swarm,
+    pub strategy: Strategy,
+}
+
+// At most four factions: homes on the left, right, top and bottom edges
+pub const MAX_FACTIONS: usize = 4;
+
+pub fn faction_name(faction: usize) -> char {
+    (b'A' + faction as u8) as char
+}
+
+pub fn faction_home(faction: usize, width: usize, height: usize) -> (usize, usize) {
+    match faction {
+        0 => (0, height / 2),
+        1 => (width - 1, height / 2),
+        2 => (width / 2, 0),
+        _ => (width / 2, height - 1),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    pub seed: u32,
+    pub width: usize,
+    pub height: usize,
+    pub swarm: Swarm,
+    pub strategy: Strategy,
+    // Further factions, each with its own home station; beyond `MAX_FACTIONS - 1` they are ignored
+    pub rivals: Vec<Faction>,
+    pub density: ResourceDensity,
+    // Faction 0 stations besides the one at (0, height / 2); positions off the map are ignored
+    pub stations: Vec<(usize, usize)>,
+    pub expansion: Option<Expansion>,
+    pub dynamics: Dynamics,
+    pub reliability: Reliability,
+    pub radio: Radio,
+    pub stigmergy: Stigmergy,
+    pub exploration: Exploration,
+    pub allocation: Allocation,
+    pub cooperation: Cooperation,
+    pub research: Research,
+}
+
+impl SimulationConfig {
+    pub fn new(seed: u32, width: usize, height: usize) -> Self {
+        Self {
+            seed,
+            width,
+            height,
+            swarm: Swarm::Random(3),
+            strategy: Strategy::Standard,
+            rivals: Vec::new(),
+            density: ResourceDensity::default(),
+            stations: Vec::new(),
+            expansion: None,
+            dynamics: Dynamics::default(),
+            reliability: Reliability::default(),
+            radio: Radio::default(),
+            stigmergy: Stigmergy::default(),
+            exploration: Exploration::default(),
+            allocation: Allocation::default(),
+            cooperation: Cooperation::default(),
+            research: Research::default(),
+        }
+    }
+}
+
+pub struct Simulation {
+    pub map: Map,
+    pub robots: Vec<Robot>,
+    pub stations: Vec<Station>,
+    pub factions: usize,
+    pub seed: u32,
+    pub tick: u64,
+    pub heatmaps: Heatmaps,
+    pub mission: Option<Mission>,
+    pub world: World,
+    pub network: Network,
+    pub pheromones: Pheromones,
+    pub frontiers: Frontiers,
+    pub auctioneer: Auctioneer,
+    pub crews: Crews,
+    pub labs: Labs,
+    pub controller: Option<ScriptController>,
+    maintenance: Maintenance,
+    expansion: Option<Expansion>,
+    // Walking distance field for each station, see `Map::distances_from`
+    station_distances: Vec<Vec<usize>>,
+    rng: StdRng,
+    events: Vec<Record>,
+}
+
+impl Simulation {
+    pub fn new(seed: u32, width: usize, height: usize, robot_count: usize) -> Self {
+        Self::from_config(SimulationConfig {
+            swarm: Swarm::Random(robot_count),
+            ..SimulationConfig::new(seed, width, height)
+        })
+    }
+
+    pub fn from_config(config: SimulationConfig) -> Self {
+        let SimulationConfig {
+            seed,
+            width,
+            height,
+            swarm,
+            strategy,
+            rivals,
+            density,
+            stations,
+            expansion,
+            dynamics,
+            reliability,
+            radio,
+            stigmergy,
+            exploration,
+            allocation,
+            cooperation,
+            research,
+        } = config;
+        let (station_x, station_y) = faction_home(0, width, height);
+
+        let mut map = Map::with_density(seed, width, height, density);
+        place_sites(&mut map, cooperation, seed);
+
+        let factions: Vec<Faction> = std::iter::once(Faction { swarm, strategy })
+            .chain(rivals)
+            .take(MAX_FACTIONS)
+            .collect();
+        let mut robots: Vec<Robot> = Vec::new();
+        for (faction, &Faction { swarm, strategy }) in factions.iter().enumerate() {
+            let (home_x, home_y) = faction_home(faction, width, height);
+            let swarm = match swarm {
+                Swarm::Random(count) => initialize_robots(count, width, height, seed),
+                Swarm::Fixed { explorers, miners, scientists } => {
+                    initialize_swarm(explorers, miners, scientists, width, height)
+                }
+            };
+            for mut robot in swarm {
+                robot.id = robots.len();
+                robot.faction = faction;
+                robot.strategy = strategy;
+                (robot.x, robot.y) = (home_x, home_y);
+                if research.is_enabled() {
+                    equip(&research, &mut robot);
+                }
+                robots.push(robot);
+            }
+        }
+
+        let mut events = vec![Record {
+            tick: 0,
+            event: Event::RunStarted { seed, width, height, station_x, station_y, density, cooperation },
+        }];
+        for robot in &robots {
+            events.push(Record {
+                tick: 0,
+                event: Event::RobotBuilt {
+                    robot: robot.id,
+                    robot_type: robot.robot_type,
+                    x: robot.x,
+                    y: robot.y,
+                    faction: robot.faction,
+                },
+            });
+        }
+
+        let world = World::new(dynamics, &map, seed);
+        let network = Network::new(radio, robots.len(), seed);
+        let frontiers = Frontiers::new(exploration, width, height, factions.len(), robots.len());
+        let crews = Crews::new(cooperation, robots.len());
+        let labs = Labs::new(research, factions.len(), seed);
+        let mut heatmaps = Heatmaps::new(width, height);
+        heatmaps.record_visits(&robots);
+
+        let mut simulation = Self {
+            map,
+            robots,
+            stations: Vec::new(),
+            factions: factions.len(),
+            seed,
+            tick: 0,
+            heatmaps,
+            mission: None,
+            world,
+            network,
+            pheromones: Pheromones::new(stigmergy, width, height),
+            frontiers,
+            auctioneer: Auctioneer::new(allocation),
+            crews,
+            labs,
+            controller: None,
+            maintenance: Maintenance::new(reliability, seed),
+            expansion,
+            station_distances: Vec::new(),
+            rng: StdRng::seed_from_u64(seed as u64),
+            events,
+        };
+
+        for faction in 0..simulation.factions {
+            let (x, y) = faction_home(faction, width, height);
+            simulation.add_station(x, y, faction);
+        }
+        for (x, y) in stations {
+            if x < width && y < height && simulation.map.grid[y][x] != Tile::Station {
+                simulation.add_station(x, y, 0);
+            }
+        }
+        simulation
+    }
+
+    fn add_station(&mut self, x: usize, y: usize, faction: usize) {
+        let id = self.stations.len();
+        self.map.grid[y][x] = Tile::Station;
+        let mut station = Station::build(id, x, y);
+        station.faction = faction;
+        self.stations.push(station);
+        self.station_distances.push(self.map.distances_from(x, y));
+
+        if id > 0 {
+            self.events.push(Record {
+                tick: self.tick,
+                event: Event::StationBuilt { station: id, x, y, faction },
+            });
+        }
+    }
+
+    // The faction's station with the shortest walk from (x, y); straight-line distance
+    // breaks ties and decides when no station can be reached at all
+    pub fn nearest_station(&self, faction: usize, x: usize, y: usize) -> usize {
+        nearest_station(&self.stations, &self.station_distances, self.map.width, faction, x, y)
+    }
+
+    // Resources of one kind delivered to all stations together
+    pub fn delivered(&self, tile: Tile) -> usize {
+        self.stations.iter().map(|station| station.count(tile)).sum()
+    }
+
+    // Resources of one kind delivered to one faction's stations
+    pub fn delivered_by(&self, faction: usize, tile: Tile) -> usize {
+        self.stations
+            .iter()
+            .filter(|station| station.faction == faction)
+            .map(|station| station.count(tile))
+            .sum()
+    }
+
+    // A faction's score: everything its robots delivered
+    pub fn score(&self, faction: usize) -> usize {
+        self.stations
+            .iter()
+            .filter(|station| station.faction == faction)
+            .map(|station| station.deliveries())
+            .sum()
+    }
+
+    pub fn step(&mut self) {
+        self.tick += 1;
+        let first_event = self.events.len();
+        self.frontiers.tick(&self.map, &mut self.robots);
+        let events = self.auctioneer.tick(&self.map, &mut self.robots);
+        self.events.extend(events.into_iter().map(|event| Record { tick: self.tick, event }));
+        if self.crews.is_enabled() {
+            self.crews.recruit(&self.map, &mut self.robots);
+        }
+        if let Some(controller) = &mut self.controller {
+            controller.tick(self.tick, &self.map, &mut self.robots, &self.network);
+        }
+
+        let mut moved = Vec::with_capacity(self.robots.len());
+        for robot in self.robots.iter_mut() {
+            let (x, y, energy_used) = (robot.x, robot.y, robot.energy_used);
+            let target = nearest_station(&self.stations, &self.station_distances, self.map.width, robot.faction, x, y);
+            let station = &mut self.stations[target];
+            let home = &self.station_distances[target];
+            let events = robot.tick(&mut self.map, &self.pheromones, station, home, &mut self.rng);
+            self.events.extend(events.into_iter().map(|event| Record { tick: self.tick, event }));
+            self.heatmaps.energy.add(x, y, (robot.energy_used - energy_used) as u32);
+            moved.push((robot.x, robot.y) != (x, y));
+        }
+        self.heatmaps.record_visits(&self.robots);
+        if self.crews.is_enabled() {
+            let events = self.crews.extract(&mut self.map, &mut self.robots);
+            self.events.extend(events.into_iter().map(|event| Record { tick: self.tick, event }));
+        }
+        let events = self.labs.tick(&self.events[first_event..], &mut self.robots);
+        self.events.extend(events.into_iter().map(|event| Record { tick: self.tick, event }));
+        // Only worth the pass over the grid when some faction actually follows the scent
+        if self.robots.iter().any(|robot| robot.strategy == Strategy::Ant) {
+            self.pheromones.tick(&self.robots);
+        }
+
+        let mut events = self.world.tick(&mut self.map, &mut self.robots);
+        // Storms move every tick and block the walk home, so the distance fields follow them
+        if !self.world.storms.is_empty() {
+            self.station_distances = self.stations.iter().map(|s| self.map.distances_from(s.x, s.y)).collect();
+        }
+        events.extend(self.maintenance.tick(&mut self.robots, &moved, &self.stations, &self.world));
+        events.extend(self.network.tick(self.tick, &self.robots, &self.stations));
+        self.events.extend(events.into_iter().map(|event| Record { tick: self.tick, event }));
+
+        for faction in 0..self.factions {
+            self.expand(faction);
+        }
+    }
+
+    // Builds at most one outpost per faction and tick once it has delivered enough minerals
+    fn expand(&mut self, faction: usize) {
+        let Some(expansion) = self.expansion else {
+            return;
+        };
+        let own = |station: &&Station| station.faction == faction;
+        let affordable = match expansion.inventory {
+            Inventory::Shared => {
+                self.stations.iter().filter(own).map(Station::available_minerals).sum::<usize>() >= expansion.cost
+            }
+            Inventory::Separate => self
+                .stations
+                .iter()
+                .filter(own)
+                .any(|s| s.available_minerals() >= expansion.cost),
+        };
+        if !affordable {
+            return;
+        }
+        let Some((x, y)) = self.outpost_site(faction, expansion.spacing) else {
+            return;
+        };
+
+        let mut own_stations = self.stations.iter_mut().filter(|s| s.faction == faction);
+        match expansion.inventory {
+            Inventory::Shared => {
+                let mut remaining = expansion.cost;
+                for station in own_stations {
+                    let spent = station.available_minerals().min(remaining);
+                    station.spent_minerals += spent;
+                    remaining -= spent;
+                }
+            }
+            Inventory::Separate => {
+                if let Some(station) = own_stations.find(|s| s.available_minerals() >= expansion.cost) {
+                    station.spent_minerals += expansion.cost;
+                }
+            }
+        }
+        self.add_station(x, y, faction);
+    }
+
+    // Outposts go on a deposit the faction mined out, as far as possible from every existing station
+    fn outpost_site(&self, faction: usize, spacing: usize) -> Option<(usize, usize)> {
+        let mut sites: Vec<(usize, usize)> = self
+            .stations
+            .iter()
+            .filter(|station| station.faction == faction)
+            .flat_map(|station| &station.discoveries)
+            .filter(|&&(x, y, tile)| tile == Tile::Mineral && self.map.grid[y][x] == Tile::Empty)
+            .map(|&(x, y, _)| (x, y))
+            .collect();
+        sites.sort_by_key(|&(x, y)| (y, x));
+
+        let width = self.map.width;
+        sites
+            .into_iter()
+            .map(|(x, y)| {
+                let distance = self.station_distances.iter().map(|d| d[y * width + x]).min().unwrap_or(usize::MAX);
+                ((x, y), distance)
+            })
+            .filter(|&(_, distance)| distance != usize::MAX && distance >= spacing)
+            .max_by_key(|&(_, distance)| distance)
+            .map(|(site, _)| site)
+    }
+
+    // The run is over once every robot is parked at one of its faction's stations and no longer returning
+    pub fn is_complete(&self) -> bool {
+        self.robots.iter().all(|r| {
+            !r.returning
+                && self
+                    .stations
+                    .iter()
+                    .any(|s| s.faction == r.faction && r.x == s.x && r.y == s.y)
+        })
+    }
+
+    pub fn mission_status(&self) -> Option<MissionStatus> {
+        self.mission.as_ref().map(|mission| mission.evaluate(self))
+    }
+
+    // With a mission the run lasts until it succeeds or fails; without one, until the swarm is parked
+    pub fn is_finished(&self) -> bool {
+        match self.mission_status() {
+            Some(status) => status != MissionStatus::InProgress,
+            None => self.is_complete(),
+        }
+    }
+
+    // Per-faction results, best first
+    pub fn display_scores(&self) {
+        let mut factions: Vec<usize> = (0..self.factions).collect();
+        factions.sort_by_key(|&faction| std::cmp::Reverse(self.score(faction)));
+
+        println!("\n🏁 Faction scores:");
+        println!("--------------------------");
+        for faction in factions {
+            let strategy = self
+                .robots
+                .iter()
+                .find(|r| r.faction == faction)
+                .map(|r| format!("{:?}", r.strategy))
+                .unwrap_or_default();
+            println!(
+                " - {} ({}): {} points ({} energy, {} minerals, {} science, {} stations)",
+                faction_name(faction),
+                strategy,
+                self.score(faction),
+                self.delivered_by(faction, Tile::Energy),
+                self.delivered_by(faction, Tile::Mineral),
+                self.delivered_by(faction, Tile::Scientific),
+                self.stations.iter().filter(|s| s.faction == faction).count()
+            );
+        }
+    }
+
+    pub fn take_events(&mut self) -> Vec<Record> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+fn nearest_station(
+    stations: &[Station],
+    distances: &[Vec<usize>],
+    width: usize,
+    faction: usize,
+    x: usize,
+    y: usize,
+) -> usize {
+    stations
+        .iter()
+        .zip(distances)
+        .filter(|(station, _)| station.faction == faction)
+        .min_by_key(|(station, distance)| (distance[y * width + x], station.x.abs_diff(x) + station.y.abs_diff(y)))
+        .map(|(station, _)| station.id)
+        .unwrap_or(0)
+}
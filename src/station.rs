@@ -1,60 +1,146 @@
 use crate::robot::Robot;
 use crate::map::Tile;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+// How building outposts draws on the minerals delivered to the stations
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Inventory {
+    // Minerals delivered anywhere form one pool
+    #[default]
+    Shared,
+    // A single station must hold enough minerals itself
+    Separate,
+}
+
+impl std::str::FromStr for Inventory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shared" => Ok(Inventory::Shared),
+            "separate" => Ok(Inventory::Separate),
+            _ => Err(format!("unknown inventory mode '{}' (expected shared or separate)", s)),
+        }
+    }
+}
+
+// Building new stations out of delivered minerals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Expansion {
+    // Minerals spent per new station
+    pub cost: usize,
+    pub inventory: Inventory,
+    // Minimum walking distance between a new station and every existing one
+    pub spacing: usize,
+}
 
 #[derive(Debug)]
 pub struct Station {
+    pub id: usize,
+    pub faction: usize,
     pub x: usize,
     pub y: usize,
+    // Every cell a resource was delivered from, once
     pub discoveries: HashSet<(usize, usize, Tile)>,
+    // Resources delivered per kind, counting every delivery from a cell that regrew
+    pub delivered: HashMap<Tile, usize>,
+    // Delivered minerals already used to build outposts
+    pub spent_minerals: usize,
 }
 
 impl Station {
     pub fn new(x: usize, y: usize) -> Self {
+        Self::build(0, x, y)
+    }
+
+    pub fn build(id: usize, x: usize, y: usize) -> Self {
         Self {
+            id,
+            faction: 0,
             x,
             y,
             discoveries: HashSet::new(),
+            delivered: HashMap::new(),
+            spent_minerals: 0,
         }
     }
 
+    pub fn count(&self, tile: Tile) -> usize {
+        self.delivered.get(&tile).copied().unwrap_or(0)
+    }
+
+    // Everything delivered, of every kind
+    pub fn deliveries(&self) -> usize {
+        self.delivered.values().sum()
+    }
+
+    pub fn deliver(&mut self, discovery: (usize, usize, Tile)) {
+        self.discoveries.insert(discovery);
+        *self.delivered.entry(discovery.2).or_insert(0) += 1;
+    }
+
+    pub fn available_minerals(&self) -> usize {
+        self.count(Tile::Mineral) - self.spent_minerals
+    }
+
     pub fn collect_discoveries(&mut self, robot: &mut Robot) {
-        if !robot.discoveries.is_empty() {
-            println!("Robot at station collected: {:?}", robot.discoveries);
-        }
-        for discovery in &robot.discoveries {
-            self.discoveries.insert(*discovery);
+        for discovery in robot.discoveries.drain(..) {
+            self.deliver(discovery);
         }
-        robot.discoveries.clear();
     }
 
     pub fn display_discoveries(&self) {
-        use std::collections::HashMap;
-    
-        let mut counts: HashMap<Tile, usize> = HashMap::new();
-    
-        for &(_, _, tile) in &self.discoveries {
-            *counts.entry(tile).or_insert(0) += 1;
-        }
+        let counts = &self.delivered;
     
         println!("\n📡 Station discoveries:");
         println!("--------------------------");
     
-        for (tile, count) in &counts {
-            let label = match tile {
-                Tile::Energy => "⚡ Energy",
-                Tile::Mineral => "⛏️ Minerals",
-                Tile::Scientific => "🔬 Scientific sites",
-                Tile::Station => "📡 Station",
-                Tile::Obstacle => "🧱 Obstacles",
-                Tile::Empty => "⬜ Empty",
-            };
-            println!(" - {}: {}", label, count);
+        let order = [
+            (Tile::Energy, "⚡ Energy"),
+            (Tile::Mineral, "⛏️ Minerals"),
+            (Tile::Scientific, "🔬 Scientific sites"),
+            (Tile::Station, "📡 Station"),
+            (Tile::Obstacle, "🧱 Obstacles"),
+            (Tile::Empty, "⬜ Empty"),
+        ];
+        for (tile, label) in order {
+            if let Some(count) = counts.get(&tile) {
+                println!(" - {}: {}", label, count);
+            }
         }
     
+        let mut positions: Vec<_> = self.discoveries.iter().collect();
+        positions.sort_by_key(|&&(x, y, _)| (y, x));
+
         println!("\n📍 All discovery positions:");
-        for (x, y, tile) in &self.discoveries {
+        for (x, y, tile) in positions {
             println!("   ({}, {}) → {:?}", x, y, tile);
         }
     }    
-}
\ No newline at end of file
+}
+
+// Prints everything delivered to any station, followed by a line per station when there are several
+pub fn display_stations(stations: &[Station]) {
+    let mut combined = Station::new(stations[0].x, stations[0].y);
+    for station in stations {
+        combined.discoveries.extend(&station.discoveries);
+        for (&tile, &count) in &station.delivered {
+            *combined.delivered.entry(tile).or_insert(0) += count;
+        }
+    }
+    combined.display_discoveries();
+
+    if stations.len() > 1 {
+        println!("\n🏠 Stations:");
+        for station in stations {
+            println!(
+                " - #{} at ({}, {}): {} deliveries, {} minerals spent on outposts",
+                station.id,
+                station.x,
+                station.y,
+                station.deliveries(),
+                station.spent_minerals
+            );
+        }
+    }
+}
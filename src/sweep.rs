@@ -0,0 +1,196 @@
+use crate::map::ResourceDensity;
+use crate::simulation::{Simulation, SimulationConfig, Swarm};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// Every combination of these values is run once per seed
+#[derive(Debug, Clone)]
+pub struct SweepGrid {
+    pub explorers: Vec<usize>,
+    pub miners: Vec<usize>,
+    pub scientists: Vec<usize>,
+    pub sizes: Vec<(usize, usize)>,
+    pub densities: Vec<f64>,
+    pub seeds: Vec<u32>,
+    pub max_ticks: u64,
+}
+
+impl Default for SweepGrid {
+    fn default() -> Self {
+        Self {
+            explorers: vec![1],
+            miners: vec![1],
+            scientists: vec![1],
+            sizes: vec![(20, 10)],
+            densities: vec![0.03],
+            seeds: (0..10).collect(),
+            max_ticks: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepPoint {
+    pub explorers: usize,
+    pub miners: usize,
+    pub scientists: usize,
+    pub width: usize,
+    pub height: usize,
+    pub density: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunOutcome {
+    pub delivered: usize,
+    pub ticks: u64,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepResult {
+    pub point: SweepPoint,
+    pub runs: usize,
+    pub completed: usize,
+    pub delivered_mean: f64,
+    pub delivered_stddev: f64,
+    // Over the completed runs only; None when none of them completed
+    pub ticks_mean: Option<f64>,
+    pub ticks_stddev: Option<f64>,
+}
+
+impl SweepGrid {
+    pub fn points(&self) -> Vec<SweepPoint> {
+        let mut points = Vec::new();
+        for &explorers in &self.explorers {
+            for &miners in &self.miners {
+                for &scientists in &self.scientists {
+                    for &(width, height) in &self.sizes {
+                        for &density in &self.densities {
+                            points.push(SweepPoint { explorers, miners, scientists, width, height, density });
+                        }
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+// Runs one headless simulation until it completes or hits `max_ticks`
+pub fn run_point(point: SweepPoint, seed: u32, max_ticks: u64) -> RunOutcome {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Fixed {
+            explorers: point.explorers,
+            miners: point.miners,
+            scientists: point.scientists,
+        },
+        density: ResourceDensity::uniform(point.density),
+        ..SimulationConfig::new(seed, point.width, point.height)
+    });
+
+    let mut completed = false;
+    while simulation.tick < max_ticks {
+        simulation.step();
+        simulation.take_events();
+        if simulation.is_complete() {
+            completed = true;
+            break;
+        }
+    }
+
+    RunOutcome {
+        delivered: simulation.stations.iter().map(|s| s.deliveries()).sum(),
+        ticks: simulation.tick,
+        completed,
+    }
+}
+
+pub fn run_sweep(grid: &SweepGrid, threads: usize) -> Vec<SweepResult> {
+    let points = grid.points();
+    let jobs: Vec<(usize, u32)> = (0..points.len())
+        .flat_map(|p| grid.seeds.iter().map(move |&seed| (p, seed)))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(vec![Vec::new(); points.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let job = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&(p, seed)) = jobs.get(job) else {
+                    break;
+                };
+                let outcome = run_point(points[p], seed, grid.max_ticks);
+                outcomes.lock().unwrap()[p].push(outcome);
+            });
+        }
+    });
+
+    points
+        .into_iter()
+        .zip(outcomes.into_inner().unwrap())
+        .map(|(point, runs)| aggregate(point, &runs))
+        .collect()
+}
+
+pub fn aggregate(point: SweepPoint, runs: &[RunOutcome]) -> SweepResult {
+    let delivered: Vec<f64> = runs.iter().map(|r| r.delivered as f64).collect();
+    let (delivered_mean, delivered_stddev) = mean_stddev(&delivered);
+    // Runs stopped at `max_ticks` never finished, so they say nothing about ticks to completion
+    let ticks: Vec<f64> = runs.iter().filter(|r| r.completed).map(|r| r.ticks as f64).collect();
+    let (ticks_mean, ticks_stddev) = if ticks.is_empty() {
+        (None, None)
+    } else {
+        let (mean, stddev) = mean_stddev(&ticks);
+        (Some(mean), Some(stddev))
+    };
+
+    SweepResult {
+        point,
+        runs: runs.len(),
+        completed: ticks.len(),
+        delivered_mean,
+        delivered_stddev,
+        ticks_mean,
+        ticks_stddev,
+    }
+}
+
+// Population standard deviation; a single run has a spread of zero
+pub fn mean_stddev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+pub fn results_csv(results: &[SweepResult]) -> String {
+    let mut csv = String::from(
+        "explorers,miners,scientists,width,height,density,runs,completed,delivered_mean,delivered_stddev,ticks_mean,ticks_stddev\n",
+    );
+    let optional = |value: Option<f64>| value.map(|v| format!("{:.3}", v)).unwrap_or_default();
+    for r in results {
+        let p = r.point;
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{:.3},{:.3},{},{}\n",
+            p.explorers,
+            p.miners,
+            p.scientists,
+            p.width,
+            p.height,
+            p.density,
+            r.runs,
+            r.completed,
+            r.delivered_mean,
+            r.delivered_stddev,
+            optional(r.ticks_mean),
+            optional(r.ticks_stddev)
+        ));
+    }
+    csv
+}
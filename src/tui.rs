@@ -0,0 +1,515 @@
+use crate::map::{Map, Tile};
+use crate::robot::{Robot, Task, MAX_HEALTH};
+use crate::simulation::{faction_name, Simulation};
+use crate::heatmap::{Heatmap, Overlay};
+use crate::mission::{MissionStatus, Objective};
+use crate::station::Station;
+use crate::viewport::{aggregate, Block, Viewport};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, stdout, Stdout, Write};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell { symbol: ' ', color: Color::Reset };
+    // Right half of a double-width glyph; never printed itself
+    pub const CONTINUATION: Cell = Cell { symbol: '\0', color: Color::Reset };
+}
+
+// Emoji used for tiles take two terminal columns
+fn is_wide(symbol: char) -> bool {
+    symbol as u32 >= 0x2600
+}
+
+// A full screen worth of cells, indexed by terminal column and row
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::BLANK; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Cell {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn put(&mut self, x: usize, y: usize, symbol: char, color: Color) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        self.cells[y * self.width + x] = Cell { symbol, color };
+        if is_wide(symbol) && x + 1 < self.width {
+            self.cells[y * self.width + x + 1] = Cell::CONTINUATION;
+        }
+    }
+
+    pub fn text(&mut self, x: usize, y: usize, text: &str, color: Color) {
+        let mut column = x;
+        for symbol in text.chars() {
+            self.put(column, y, symbol, color);
+            column += if is_wide(symbol) { 2 } else { 1 };
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Scroll(isize, isize),
+    Follow,
+    ZoomIn,
+    ZoomOut,
+    Overlay,
+    // Jump this many ticks back or forward; only replays can
+    Seek(i64),
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Status {
+    pub paused: bool,
+    pub delay: Duration,
+    // Draw only every Nth tick; 1 draws every tick
+    pub render_every: u64,
+}
+
+impl Status {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            paused: false,
+            delay,
+            render_every: 1,
+        }
+    }
+
+    pub fn should_draw(&self, tick: u64) -> bool {
+        self.paused || tick.is_multiple_of(self.render_every.max(1))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Glyphs {
+    Emoji,
+    Ascii,
+}
+
+// Owns the terminal while the simulation runs; dropping it restores the terminal,
+// including when unwinding from a panic
+pub struct Tui {
+    stdout: Stdout,
+    previous: Option<Frame>,
+}
+
+impl Tui {
+    pub fn enter() -> io::Result<Self> {
+        let mut stdout = stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, Hide, Clear(ClearType::All))?;
+        Ok(Self { stdout, previous: None })
+    }
+
+    pub fn size(&self) -> io::Result<(usize, usize)> {
+        let (columns, rows) = terminal::size()?;
+        Ok((columns as usize, rows as usize))
+    }
+
+    // Only cells that differ from the previously drawn frame are written
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        let full = match &self.previous {
+            Some(previous) => previous.width != frame.width || previous.height != frame.height,
+            None => true,
+        };
+        if full {
+            queue!(self.stdout, Clear(ClearType::All))?;
+        }
+
+        let mut color = None;
+        for y in 0..frame.height {
+            let mut cursor = None;
+            for x in 0..frame.width {
+                let cell = frame.get(x, y);
+                let changed = full || self.previous.as_ref().map(|p| p.get(x, y)) != Some(cell);
+                if !changed || cell == Cell::CONTINUATION {
+                    continue;
+                }
+
+                if cursor != Some(x) {
+                    queue!(self.stdout, MoveTo(x as u16, y as u16))?;
+                }
+                if color != Some(cell.color) {
+                    queue!(self.stdout, SetForegroundColor(cell.color))?;
+                    color = Some(cell.color);
+                }
+                queue!(self.stdout, Print(cell.symbol))?;
+                cursor = Some(x + if is_wide(cell.symbol) { 2 } else { 1 });
+            }
+        }
+
+        queue!(self.stdout, ResetColor)?;
+        self.stdout.flush()?;
+        self.previous = Some(frame.clone());
+        Ok(())
+    }
+
+    // Waits up to `timeout` for a key press that maps to a control
+    pub fn poll_control(&self, timeout: Duration) -> io::Result<Option<Control>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        let Event::Key(key) = event::read()? else {
+            return Ok(None);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(None);
+        }
+
+        let control = match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Control::Quit),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Control::Quit),
+            KeyCode::Char(' ') | KeyCode::Char('p') => Some(Control::TogglePause),
+            KeyCode::Char('n') | KeyCode::Char('.') => Some(Control::Step),
+            KeyCode::Char('+') | KeyCode::Char('=') => Some(Control::Faster),
+            KeyCode::Char('-') => Some(Control::Slower),
+            KeyCode::Char('f') => Some(Control::Follow),
+            KeyCode::Char('z') => Some(Control::ZoomIn),
+            KeyCode::Char('x') => Some(Control::ZoomOut),
+            KeyCode::Char('h') => Some(Control::Overlay),
+            KeyCode::Char(',') => Some(Control::Seek(-1)),
+            KeyCode::Char('[') => Some(Control::Seek(-10)),
+            KeyCode::Char(']') => Some(Control::Seek(10)),
+            KeyCode::Home => Some(Control::Seek(i64::MIN)),
+            KeyCode::End => Some(Control::Seek(i64::MAX)),
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                let step = if key.modifiers.contains(KeyModifiers::SHIFT) { 10 } else { 1 };
+                let (dx, dy) = match key.code {
+                    KeyCode::Up => (0, -step),
+                    KeyCode::Down => (0, step),
+                    KeyCode::Left => (-step, 0),
+                    _ => (step, 0),
+                };
+                Some(Control::Scroll(dx, dy))
+            }
+            _ => None,
+        };
+        Ok(control)
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, ResetColor, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn robot_state(robot: &Robot, map: &Map, stations: &[Station]) -> &'static str {
+    if robot.is_broken() {
+        "broken"
+    } else if robot.returning && robot.energy == 0 {
+        "stranded"
+    } else if robot.returning {
+        "returning"
+    } else if stations.iter().any(|s| robot.x == s.x && robot.y == s.y) {
+        if robot.health < MAX_HEALTH {
+            "repairing"
+        } else {
+            "docked"
+        }
+    } else if robot.is_analysing(map) {
+        "analysing"
+    } else if robot.task == Task::Assigned(robot.x, robot.y) {
+        "waiting"
+    } else if !robot.faults.is_empty() {
+        "faulty"
+    } else {
+        "working"
+    }
+}
+
+pub const PANEL_WIDTH: usize = 48;
+const MINIMAP_HEIGHT: usize = 10;
+
+// Lays out the visible part of the map on the left and the robot/station panel on the
+// right, filling a terminal of `columns`×`rows`. The viewport is resized to fit first.
+pub fn compose(
+    simulation: &Simulation,
+    status: &Status,
+    viewport: &mut Viewport,
+    (columns, rows): (usize, usize),
+    glyphs: Glyphs,
+) -> Frame {
+    let map = &simulation.map;
+    let robots = &simulation.robots;
+    let mut frame = Frame::new(columns, rows);
+
+    viewport.fit(
+        columns.saturating_sub(PANEL_WIDTH + 2),
+        rows.saturating_sub(1),
+        map,
+        robots,
+    );
+    let zoom = viewport.zoom;
+    let heatmap = viewport.overlay.select(&simulation.heatmaps, &simulation.pheromones);
+    let heat_max = heatmap.map(|h| visible_max(h, viewport)).unwrap_or(0);
+    for sy in 0..viewport.height {
+        for sx in 0..viewport.width {
+            let (x, y) = (viewport.x + sx * zoom, viewport.y + sy * zoom);
+            let block = aggregate(map, robots, x, y, zoom, zoom);
+            let (symbol, color) = match (heatmap, block) {
+                (Some(heatmap), Block::Tile(tile)) if tile != Tile::Obstacle => {
+                    heat_symbol(heatmap.block_sum(x, y, zoom, zoom), heat_max, glyphs)
+                }
+                (None, Block::Tile(Tile::Empty)) if has_relay(simulation, x, y, zoom) => match glyphs {
+                    Glyphs::Emoji => ('📶', Color::Magenta),
+                    Glyphs::Ascii => ('r', Color::Magenta),
+                },
+                (None, Block::Tile(tile))
+                    if tile != Tile::Station && simulation.world.storm_at(x + zoom / 2, y + zoom / 2) =>
+                {
+                    match glyphs {
+                        Glyphs::Emoji => ('🌀', Color::Magenta),
+                        Glyphs::Ascii => ('~', Color::Magenta),
+                    }
+                }
+                _ => match glyphs {
+                    Glyphs::Emoji => block.symbol(),
+                    Glyphs::Ascii => block.ascii_symbol(),
+                },
+            };
+            frame.put(sx * 2, sy, symbol, color);
+        }
+    }
+
+    let panel_x = viewport.width * 2 + 2;
+    let state = if status.paused { "paused" } else { "running" };
+    frame.text(
+        panel_x,
+        0,
+        &if status.render_every > 1 {
+            format!("Tick {:<6} {:<8} fast, drawing every {}", simulation.tick, state, status.render_every)
+        } else {
+            format!("Tick {:<6} {:<8} {}ms/tick", simulation.tick, state, status.delay.as_millis())
+        },
+        Color::White,
+    );
+
+    if simulation.factions > 1 {
+        let scores: Vec<String> = (0..simulation.factions)
+            .map(|faction| format!("{} {}", faction_name(faction), simulation.score(faction)))
+            .collect();
+        frame.text(panel_x, 1, &format!("Scores  {}", scores.join("  ")), Color::Green);
+    }
+
+    let count = |tile| simulation.delivered(tile);
+    let stations = match simulation.stations.len() {
+        1 => "Station".to_string(),
+        n => format!("Stations ({})", n),
+    };
+    let stations = if simulation.network.radio.is_enabled() {
+        format!(
+            "{:<14}Radio {}/{} in contact",
+            stations,
+            simulation.network.in_contact(),
+            robots.iter().filter(|r| !r.is_broken()).count()
+        )
+    } else {
+        stations
+    };
+    frame.text(panel_x, 2, &stations, Color::Magenta);
+    frame.text(
+        panel_x,
+        3,
+        &format!(
+            "Energy {:<4} Minerals {:<4} Science {:<4}",
+            count(Tile::Energy),
+            count(Tile::Mineral),
+            count(Tile::Scientific)
+        ),
+        Color::White,
+    );
+
+    if let Some(mission) = &simulation.mission {
+        let mut line = String::from("Mission");
+        for objective in &mission.objectives {
+            let (current, target) = objective.progress(simulation);
+            let (label, unit) = match objective {
+                Objective::Deliver { tile, .. } => (format!("{:?}", tile), ""),
+                Objective::Explore(_) => ("Explored".to_string(), "%"),
+            };
+            line.push_str(&format!(" {} {}/{}{}", label, current.floor(), target, unit));
+        }
+        if let Some(limit) = mission.tick_limit {
+            line.push_str(&format!(" by tick {}", limit));
+        }
+        let color = match mission.evaluate(simulation) {
+            MissionStatus::InProgress => Color::Cyan,
+            MissionStatus::Accomplished => Color::Green,
+            MissionStatus::Failed(_) => Color::Red,
+        };
+        frame.text(panel_x, 4, &line, color);
+    }
+
+    let follow = match viewport.follow {
+        Some(id) => format!("following #{}", id),
+        None => "free".to_string(),
+    };
+    frame.text(
+        panel_x,
+        5,
+        &format!("View {},{} of {}x{}  zoom 1:{}  {}", viewport.x, viewport.y, map.width, map.height, zoom, follow),
+        Color::White,
+    );
+    if viewport.overlay != Overlay::None {
+        frame.text(
+            panel_x,
+            6,
+            &format!("Heatmap: {:?} (max {} per cell)", viewport.overlay, heatmap.map(|h| h.max()).unwrap_or(0)),
+            Color::Yellow,
+        );
+    }
+
+    let mut row = 7;
+    if simulation.labs.research.is_enabled() {
+        let labs: Vec<String> = simulation
+            .labs
+            .labs
+            .iter()
+            .enumerate()
+            .map(|(faction, lab)| {
+                let next = match lab.next_upgrade() {
+                    Some((upgrade, cost)) => format!("{}/{} to {}", lab.available(), cost, upgrade),
+                    None => format!("{} spare", lab.available()),
+                };
+                if simulation.factions > 1 {
+                    format!("{} {}", faction_name(faction), next)
+                } else {
+                    next
+                }
+            })
+            .collect();
+        frame.text(panel_x, row, &format!("Research {}", labs.join("  ")), Color::Cyan);
+        row += 1;
+    }
+    let (span_x, span_y) = viewport.span();
+    if span_x < map.width || span_y < map.height {
+        row += draw_minimap(&mut frame, simulation, viewport, panel_x, row) + 1;
+    }
+
+    frame.text(panel_x, row, "Robots", Color::Blue);
+    let radio = simulation.network.radio.is_enabled();
+    let header = " #  type       energy state      cargo   hp";
+    let header = if radio { format!("{} link", header) } else { header.to_string() };
+    frame.text(panel_x, row + 1, &header, Color::DarkGrey);
+    for (i, robot) in robots.iter().enumerate() {
+        let id = if simulation.factions > 1 {
+            format!("{}{}", faction_name(robot.faction), robot.id)
+        } else {
+            robot.id.to_string()
+        };
+        let mut line = format!(
+            "{:>2}  {:<10} {:>6} {:<10} {:>5} {:>4}",
+            id,
+            format!("{:?}", robot.robot_type),
+            robot.energy,
+            robot_state(robot, map, &simulation.stations),
+            robot.discoveries.len(),
+            robot.health
+        );
+        if radio {
+            match simulation.network.link(robot.id) {
+                Some(link) => line.push_str(&format!(" {:>4}", link.hops)),
+                None => line.push_str("    -"),
+            }
+        }
+        frame.text(panel_x, row + 2 + i, &line, Color::White);
+    }
+
+    frame.text(
+        0,
+        rows.saturating_sub(1),
+        "space: pause  n: step  +/-: speed  arrows: scroll  f: follow  z/x: zoom  h: heatmap  q: quit",
+        Color::DarkGrey,
+    );
+
+    frame
+}
+
+// Whether a relay beacon sits in the zoom×zoom block at (x, y)
+fn has_relay(simulation: &Simulation, x: usize, y: usize, zoom: usize) -> bool {
+    simulation
+        .network
+        .radio
+        .relays
+        .iter()
+        .any(|&(rx, ry)| (x..x + zoom).contains(&rx) && (y..y + zoom).contains(&ry))
+}
+
+// Largest block value currently on screen, so the ramp uses the full range at any zoom
+fn visible_max(heatmap: &Heatmap, viewport: &Viewport) -> u32 {
+    let zoom = viewport.zoom;
+    let mut max = 0;
+    for sy in 0..viewport.height {
+        for sx in 0..viewport.width {
+            max = max.max(heatmap.block_sum(viewport.x + sx * zoom, viewport.y + sy * zoom, zoom, zoom));
+        }
+    }
+    max
+}
+
+fn heat_symbol(value: u32, max: u32, glyphs: Glyphs) -> (char, Color) {
+    const COLORS: [Color; 5] = [Color::DarkGrey, Color::Blue, Color::Green, Color::Yellow, Color::Red];
+    let ramp = match glyphs {
+        Glyphs::Emoji => ['·', '░', '▒', '▓', '█'],
+        Glyphs::Ascii => ['.', ':', '+', '*', '@'],
+    };
+
+    let level = if value == 0 || max == 0 {
+        0
+    } else {
+        1 + ((value as f64).ln_1p() / (max as f64).ln_1p() * 3.0).round() as usize
+    };
+    (ramp[level.min(4)], COLORS[level.min(4)])
+}
+
+// Draws the whole map shrunk into the panel, dimming the parts outside the viewport.
+// Returns the number of rows used.
+fn draw_minimap(frame: &mut Frame, simulation: &Simulation, viewport: &Viewport, x: usize, y: usize) -> usize {
+    let map = &simulation.map;
+    let block_w = map.width.div_ceil(PANEL_WIDTH - 2).max(1);
+    let block_h = map.height.div_ceil(MINIMAP_HEIGHT).max(1);
+
+    let rows = map.height.div_ceil(block_h);
+    for my in 0..rows {
+        for mx in 0..map.width.div_ceil(block_w) {
+            let (cell_x, cell_y) = (mx * block_w, my * block_h);
+            let block = aggregate(map, &simulation.robots, cell_x, cell_y, block_w, block_h);
+            let (symbol, color) = block.ascii_symbol();
+            let color = if viewport.contains(cell_x, cell_y) { color } else { Color::DarkGrey };
+            frame.put(x + mx, y + my, symbol, color);
+        }
+    }
+    rows
+}
@@ -0,0 +1,161 @@
+use crate::heatmap::Overlay;
+use crate::map::{robot_symbol, tile_ascii, tile_symbol, Map, Tile};
+use crate::robot::{Robot, RobotType};
+use crossterm::style::Color;
+
+pub const MAX_ZOOM: usize = 16;
+
+// The window of the map shown on screen. `x`/`y` is the top-left map cell, `width`/`height`
+// are measured in screen cells, and each screen cell covers `zoom`×`zoom` map cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub zoom: usize,
+    pub follow: Option<usize>,
+    pub overlay: Overlay,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            zoom: 1,
+            follow: None,
+            overlay: Overlay::None,
+        }
+    }
+}
+
+impl Viewport {
+    // Sizes the view to the `columns`×`rows` terminal area available for the map,
+    // then re-centres on the followed robot and keeps the view inside the map
+    pub fn fit(&mut self, columns: usize, rows: usize, map: &Map, robots: &[Robot]) {
+        self.width = (columns / 2).min(map.width.div_ceil(self.zoom)).max(1);
+        self.height = rows.min(map.height.div_ceil(self.zoom)).max(1);
+
+        if let Some(robot) = self.follow.and_then(|id| robots.iter().find(|r| r.id == id)) {
+            let (span_x, span_y) = self.span();
+            self.x = robot.x.saturating_sub(span_x / 2);
+            self.y = robot.y.saturating_sub(span_y / 2);
+        }
+        self.clamp(map);
+    }
+
+    // Number of map cells covered horizontally and vertically
+    pub fn span(&self) -> (usize, usize) {
+        (self.width * self.zoom, self.height * self.zoom)
+    }
+
+    pub fn scroll(&mut self, dx: isize, dy: isize, map: &Map) {
+        self.follow = None;
+        self.x = self.x.saturating_add_signed(dx * self.zoom as isize);
+        self.y = self.y.saturating_add_signed(dy * self.zoom as isize);
+        self.clamp(map);
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom / 2).max(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom * 2).min(MAX_ZOOM);
+    }
+
+    // Cycles through following each robot in turn, then back to free scrolling
+    pub fn cycle_follow(&mut self, robots: &[Robot]) {
+        self.follow = match self.follow {
+            None => robots.first().map(|r| r.id),
+            Some(id) => robots
+                .iter()
+                .position(|r| r.id == id)
+                .and_then(|i| robots.get(i + 1))
+                .map(|r| r.id),
+        };
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        let (span_x, span_y) = self.span();
+        x >= self.x && x < self.x + span_x && y >= self.y && y < self.y + span_y
+    }
+
+    fn clamp(&mut self, map: &Map) {
+        let (span_x, span_y) = self.span();
+        self.x = self.x.min(map.width.saturating_sub(span_x));
+        self.y = self.y.min(map.height.saturating_sub(span_y));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Block {
+    Robot(RobotType),
+    Tile(Tile),
+}
+
+impl Block {
+    pub fn symbol(self) -> (char, Color) {
+        match self {
+            Block::Robot(robot_type) => (robot_symbol(robot_type), Color::Blue),
+            Block::Tile(tile) => tile_symbol(tile),
+        }
+    }
+
+    // Single-width symbol, used by the minimap and the no-emoji renderers
+    pub fn ascii_symbol(self) -> (char, Color) {
+        match self {
+            Block::Robot(_) => self.symbol(),
+            Block::Tile(tile) => (tile_ascii(tile), tile_symbol(tile).1),
+        }
+    }
+}
+
+// Summarises a block of map cells: robots first, then the station, then the
+// rarest resource present, then obstacles if they fill most of the block
+pub fn aggregate(map: &Map, robots: &[Robot], x: usize, y: usize, width: usize, height: usize) -> Block {
+    let x_end = (x + width).min(map.width);
+    let y_end = (y + height).min(map.height);
+    if x >= x_end || y >= y_end {
+        return Block::Tile(Tile::Empty);
+    }
+
+    if let Some(robot) = robots
+        .iter()
+        .find(|r| r.x >= x && r.x < x_end && r.y >= y && r.y < y_end && map.grid[r.y][r.x] != Tile::Station)
+    {
+        return Block::Robot(robot.robot_type);
+    }
+
+    let mut obstacles = 0;
+    let mut best = Tile::Empty;
+    let priority = |tile| match tile {
+        Tile::Station => 5,
+        Tile::LargeScientific => 4,
+        Tile::LargeMineral => 3,
+        Tile::Scientific => 4,
+        Tile::Mineral => 3,
+        Tile::Energy => 2,
+        Tile::Obstacle | Tile::Empty => 0,
+    };
+
+    for row in &map.grid[y..y_end] {
+        for &tile in &row[x..x_end] {
+            if tile == Tile::Obstacle {
+                obstacles += 1;
+            }
+            if priority(tile) > priority(best) {
+                best = tile;
+            }
+        }
+    }
+
+    let cells = (x_end - x) * (y_end - y);
+    if best == Tile::Empty && obstacles * 2 > cells {
+        best = Tile::Obstacle;
+    }
+    Block::Tile(best)
+}
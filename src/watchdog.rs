@@ -0,0 +1,138 @@
+use crate::event::{Event, Record};
+use crate::simulation::Simulation;
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    // The swarm parked, or the mission succeeded or failed
+    Finished,
+    TickLimit(u64),
+    Timeout(Duration),
+    // No robot has made progress within the stuck window
+    AllStuck,
+    Interrupted,
+    Quit,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopReason::Finished => write!(f, "simulation completed"),
+            StopReason::TickLimit(limit) => write!(f, "tick limit of {} reached", limit),
+            StopReason::Timeout(timeout) => write!(f, "timed out after {:.1}s", timeout.as_secs_f64()),
+            StopReason::AllStuck => write!(f, "every robot is stuck"),
+            StopReason::Interrupted => write!(f, "interrupted"),
+            StopReason::Quit => write!(f, "stopped by user"),
+        }
+    }
+}
+
+// Bounds on how long a run may go on
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Limits {
+    pub max_ticks: Option<u64>,
+    pub timeout: Option<Duration>,
+}
+
+impl Limits {
+    pub fn check(&self, simulation: &Simulation, started: Instant) -> Option<StopReason> {
+        if let Some(limit) = self.max_ticks {
+            if simulation.tick >= limit {
+                return Some(StopReason::TickLimit(limit));
+            }
+        }
+        if let Some(timeout) = self.timeout {
+            if started.elapsed() >= timeout {
+                return Some(StopReason::Timeout(timeout));
+            }
+        }
+        None
+    }
+}
+
+// Flags robots that have not reached a cell they never stood on before,
+// harvested or delivered anything for `window` ticks
+pub struct StuckDetector {
+    window: u64,
+    visited: Vec<HashSet<(usize, usize)>>,
+    last_progress: Vec<u64>,
+    stuck: Vec<bool>,
+}
+
+impl StuckDetector {
+    pub fn new(simulation: &Simulation, window: u64) -> Self {
+        let count = simulation.robots.len();
+        Self {
+            window: window.max(1),
+            visited: simulation.robots.iter().map(|r| HashSet::from([(r.x, r.y)])).collect(),
+            last_progress: vec![simulation.tick; count],
+            stuck: vec![false; count],
+        }
+    }
+
+    // Folds one tick of events in; returns a `RobotStuck` record for each robot that just got stuck
+    pub fn record(&mut self, simulation: &Simulation, records: &[Record]) -> Vec<Record> {
+        for record in records {
+            let robot = match record.event {
+                Event::RobotMoved { robot, to, .. } => {
+                    if !self.visited[robot].insert(to) {
+                        continue;
+                    }
+                    robot
+                }
+                Event::ResourceHarvested { robot, .. } | Event::DiscoveryDelivered { robot, .. } => robot,
+                _ => continue,
+            };
+            self.last_progress[robot] = record.tick;
+            self.stuck[robot] = false;
+        }
+
+        let mut newly_stuck = Vec::new();
+        for robot in &simulation.robots {
+            let since = self.last_progress[robot.id];
+            if !self.stuck[robot.id] && simulation.tick - since >= self.window {
+                self.stuck[robot.id] = true;
+                newly_stuck.push(Record {
+                    tick: simulation.tick,
+                    event: Event::RobotStuck { robot: robot.id, x: robot.x, y: robot.y, since },
+                });
+            }
+        }
+        newly_stuck
+    }
+
+    pub fn is_stuck(&self, robot: usize) -> bool {
+        self.stuck[robot]
+    }
+
+    pub fn all_stuck(&self) -> bool {
+        !self.stuck.is_empty() && self.stuck.iter().all(|&s| s)
+    }
+
+    // Robots currently stuck, with the tick they last made progress
+    pub fn stuck_robots(&self) -> Vec<(usize, u64)> {
+        (0..self.stuck.len())
+            .filter(|&robot| self.stuck[robot])
+            .map(|robot| (robot, self.last_progress[robot]))
+            .collect()
+    }
+
+    pub fn display_report(&self, simulation: &Simulation) {
+        let stuck = self.stuck_robots();
+        if stuck.is_empty() {
+            return;
+        }
+
+        println!("\n⚠️ Stuck robots (no progress for {} ticks):", self.window);
+        println!("--------------------------");
+        for (id, since) in stuck {
+            let robot = &simulation.robots[id];
+            println!(
+                " - #{} {:?} at ({}, {}), last progress at tick {}",
+                id, robot.robot_type, robot.x, robot.y, since
+            );
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::event::Event;
+use crate::map::{Map, Tile};
+use crate::robot::Robot;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Optional changes to the map over time; everything is off by default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dynamics {
+    // Chance per tick that a harvested energy cell grows back
+    pub energy_regrowth: f64,
+    // Chance per tick that a new mineral vein appears somewhere
+    pub mineral_veins: f64,
+    pub storms: usize,
+    pub storm_radius: usize,
+    // Energy drained per tick from every robot caught in a storm
+    pub storm_damage: usize,
+}
+
+impl Default for Dynamics {
+    fn default() -> Self {
+        Self {
+            energy_regrowth: 0.0,
+            mineral_veins: 0.0,
+            storms: 0,
+            storm_radius: 2,
+            storm_damage: 1,
+        }
+    }
+}
+
+impl Dynamics {
+    // A reasonable mix of everything, used by `--dynamic`
+    pub fn preset() -> Self {
+        Self {
+            energy_regrowth: 0.01,
+            mineral_veins: 0.05,
+            storms: 2,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Storm {
+    pub x: usize,
+    pub y: usize,
+    pub dx: isize,
+    pub dy: isize,
+    pub radius: usize,
+}
+
+impl Storm {
+    pub fn covers(&self, x: usize, y: usize) -> bool {
+        self.x.abs_diff(x) <= self.radius && self.y.abs_diff(y) <= self.radius
+    }
+
+    // Drifts one cell per tick, bouncing off the map edges and now and then changing course
+    fn drift(&mut self, width: usize, height: usize, rng: &mut StdRng) {
+        if rng.gen_bool(0.1) {
+            (self.dx, self.dy) = random_heading(rng);
+        }
+        if self.x == 0 && self.dx < 0 || self.x + 1 >= width && self.dx > 0 {
+            self.dx = -self.dx;
+        }
+        if self.y == 0 && self.dy < 0 || self.y + 1 >= height && self.dy > 0 {
+            self.dy = -self.dy;
+        }
+        self.x = (self.x as isize + self.dx).clamp(0, width as isize - 1) as usize;
+        self.y = (self.y as isize + self.dy).clamp(0, height as isize - 1) as usize;
+    }
+}
+
+fn random_heading(rng: &mut StdRng) -> (isize, isize) {
+    loop {
+        let heading = (rng.gen_range(-1..=1), rng.gen_range(-1..=1));
+        if heading != (0, 0) {
+            return heading;
+        }
+    }
+}
+
+// The moving parts of the world, driven by their own seeded RNG so that turning
+// dynamics on or off does not change how the robots move
+pub struct World {
+    pub dynamics: Dynamics,
+    pub storms: Vec<Storm>,
+    energy_sites: Vec<(usize, usize)>,
+    rng: StdRng,
+}
+
+impl World {
+    pub fn new(dynamics: Dynamics, map: &Map, seed: u32) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed as u64 ^ 0x5eed_3071d);
+
+        let mut energy_sites = Vec::new();
+        for (y, row) in map.grid.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                if tile == Tile::Energy {
+                    energy_sites.push((x, y));
+                }
+            }
+        }
+
+        let storms = (0..dynamics.storms)
+            .map(|_| {
+                let (dx, dy) = random_heading(&mut rng);
+                Storm {
+                    x: rng.gen_range(0..map.width),
+                    y: rng.gen_range(0..map.height),
+                    dx,
+                    dy,
+                    radius: dynamics.storm_radius,
+                }
+            })
+            .collect();
+
+        Self { dynamics, storms, energy_sites, rng }
+    }
+
+    pub fn storm_at(&self, x: usize, y: usize) -> bool {
+        self.storms.iter().any(|storm| storm.covers(x, y))
+    }
+
+    // Advances regrowth, veins and storms by one tick
+    pub fn tick(&mut self, map: &mut Map, robots: &mut [Robot]) -> Vec<Event> {
+        let mut events = Vec::new();
+        let occupied = |x: usize, y: usize| robots.iter().any(|r| r.x == x && r.y == y);
+
+        if self.dynamics.energy_regrowth > 0.0 {
+            for &(x, y) in &self.energy_sites {
+                if map.grid[y][x] == Tile::Empty && !occupied(x, y) && self.rng.gen_bool(self.dynamics.energy_regrowth) {
+                    map.grid[y][x] = Tile::Energy;
+                    events.push(Event::ResourceSpawned { x, y, tile: Tile::Energy });
+                }
+            }
+        }
+
+        if self.dynamics.mineral_veins > 0.0 && self.rng.gen_bool(self.dynamics.mineral_veins) {
+            let (mut x, mut y) = (self.rng.gen_range(0..map.width), self.rng.gen_range(0..map.height));
+            // A vein is a short random walk of up to three cells
+            for _ in 0..3 {
+                if map.grid[y][x] == Tile::Empty && !occupied(x, y) {
+                    map.grid[y][x] = Tile::Mineral;
+                    events.push(Event::ResourceSpawned { x, y, tile: Tile::Mineral });
+                }
+                let (dx, dy) = random_heading(&mut self.rng);
+                x = (x as isize + dx).clamp(0, map.width as isize - 1) as usize;
+                y = (y as isize + dy).clamp(0, map.height as isize - 1) as usize;
+            }
+        }
+
+        for storm in self.storms.iter_mut() {
+            storm.drift(map.width, map.height, &mut self.rng);
+        }
+        // Nothing enters a storm; robots it drifts over wait inside until it has passed
+        if !self.storms.is_empty() {
+            for (index, blocked) in map.blocked.iter_mut().enumerate() {
+                *blocked = self.storm_at(index % map.width, index / map.width);
+            }
+        }
+        for robot in robots.iter_mut() {
+            if robot.energy > 0 && self.storms.iter().any(|storm| storm.covers(robot.x, robot.y)) {
+                let amount = robot.energy.min(self.dynamics.storm_damage);
+                robot.energy -= amount;
+                events.push(Event::RobotDamaged { robot: robot.id, x: robot.x, y: robot.y, amount });
+            }
+        }
+
+        events
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use robot_swarm::auction::{Allocation, Auctioneer};
+use robot_swarm::event::Event;
+use robot_swarm::map::Tile;
+use robot_swarm::robot::{Robot, RobotType, Task};
+use common::empty_map;
+
+fn miner(id: usize, x: usize, y: usize, energy: usize) -> Robot {
+    let mut robot = Robot::build(id, RobotType::Miner, x, y);
+    robot.energy = energy;
+    robot
+}
+
+#[test]
+fn test_nearest_allocation_leaves_robots_alone() {
+    let mut map = empty_map(20, 10);
+    map.grid[2][2] = Tile::Mineral;
+    let mut robots = vec![miner(0, 0, 0, 20)];
+
+    let events = Auctioneer::new(Allocation::Nearest).tick(&map, &mut robots);
+
+    assert_eq!("auction".parse(), Ok(Allocation::Auction));
+    assert!(events.is_empty());
+    assert_eq!(robots[0].task, Task::Unmanaged);
+}
+
+#[test]
+fn test_closest_robot_wins_the_resource() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][10] = Tile::Mineral;
+    let mut robots = vec![miner(0, 2, 5, 20), miner(1, 8, 5, 20)];
+
+    let events = Auctioneer::new(Allocation::Auction).tick(&map, &mut robots);
+
+    assert_eq!(robots[0].task, Task::Idle);
+    assert_eq!(robots[1].task, Task::Assigned(10, 5));
+    assert_eq!(events, vec![Event::TaskAssigned { robot: 1, x: 10, y: 5 }]);
+}
+
+#[test]
+fn test_energy_breaks_ties_and_limits_reach() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][10] = Tile::Mineral;
+    map.grid[0][19] = Tile::Mineral;
+    let mut robots = vec![miner(0, 6, 5, 8), miner(1, 14, 5, 12)];
+
+    Auctioneer::new(Allocation::Auction).tick(&map, &mut robots);
+
+    // Both are 4 cells away; robot 0 cannot reach (19, 0) on 8 energy either
+    assert_eq!(robots[1].task, Task::Assigned(10, 5));
+    assert_eq!(robots[0].task, Task::Idle);
+}
+
+#[test]
+fn test_every_resource_goes_to_one_robot() {
+    let mut map = empty_map(20, 10);
+    for x in [4, 9, 14] {
+        map.grid[3][x] = Tile::Mineral;
+    }
+    let mut robots = vec![miner(0, 0, 3, 30), miner(1, 1, 3, 30), miner(2, 2, 3, 30)];
+    let mut auctioneer = Auctioneer::new(Allocation::Auction);
+
+    assert_eq!(auctioneer.tick(&map, &mut robots).len(), 3);
+    let mut targets: Vec<Task> = robots.iter().map(|r| r.task).collect();
+    targets.dedup();
+    assert_eq!(targets.len(), 3);
+
+    // Unchanged assignments are not announced again
+    assert!(auctioneer.tick(&map, &mut robots).is_empty());
+    assert_eq!(auctioneer.assignments, 3);
+}
+
+#[test]
+fn test_only_standard_miners_and_scientists_bid() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][5] = Tile::Mineral;
+    map.grid[6][6] = Tile::Scientific;
+    let mut returning = miner(2, 4, 5, 20);
+    returning.returning = true;
+    let mut robots = vec![
+        Robot::build(0, RobotType::Explorer, 5, 4),
+        Robot::build(1, RobotType::Scientist, 6, 4),
+        returning,
+    ];
+    robots[1].energy = 20;
+
+    Auctioneer::new(Allocation::Auction).tick(&map, &mut robots);
+
+    assert_eq!(robots[0].task, Task::Unmanaged);
+    assert_eq!(robots[1].task, Task::Assigned(6, 6));
+    assert_eq!(robots[2].task, Task::Unmanaged);
+}
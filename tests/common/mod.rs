@@ -0,0 +1,10 @@
+use robot_swarm::map::{Map, Tile};
+
+// A map without obstacles, resources or a station
+pub fn empty_map(width: usize, height: usize) -> Map {
+    let mut map = Map::new(42, width, height);
+    for row in map.grid.iter_mut() {
+        row.fill(Tile::Empty);
+    }
+    map
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use robot_swarm::cooperation::{place_sites, Cooperation, Crews};
+use robot_swarm::event::Event;
+use robot_swarm::map::{Map, Tile};
+use robot_swarm::robot::{Robot, RobotType, Task};
+use robot_swarm::simulation::{Simulation, SimulationConfig, Swarm};
+use common::empty_map;
+
+fn robot(id: usize, robot_type: RobotType, x: usize, y: usize) -> Robot {
+    let mut robot = Robot::build(id, robot_type, x, y);
+    robot.energy = 50;
+    robot
+}
+
+fn count(map: &Map, tile: Tile) -> usize {
+    map.grid.iter().flatten().filter(|&&t| t == tile).count()
+}
+
+#[test]
+fn test_place_sites_upgrades_resources() {
+    let plain = Map::new(42, 40, 20);
+    let mut map = Map::new(42, 40, 20);
+
+    place_sites(&mut map, Cooperation::default(), 42);
+    assert_eq!(map.grid, plain.grid);
+
+    place_sites(&mut map, Cooperation { large_minerals: 2, large_science: 1 }, 42);
+    assert_eq!(count(&map, Tile::LargeMineral), 2);
+    assert_eq!(count(&map, Tile::LargeScientific), 1);
+    assert_eq!(count(&map, Tile::Mineral), count(&plain, Tile::Mineral) - 2);
+}
+
+#[test]
+fn test_large_sites_cannot_be_worked_alone() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][5] = Tile::LargeMineral;
+    map.grid[5][6] = Tile::LargeScientific;
+    let mut miner = robot(0, RobotType::Miner, 5, 5);
+    let mut explorer = robot(1, RobotType::Explorer, 6, 5);
+
+    miner.perform_action(&mut map);
+    explorer.perform_action(&mut map);
+
+    assert!(miner.discoveries.is_empty() && explorer.discoveries.is_empty());
+    assert_eq!(map.grid[5][5], Tile::LargeMineral);
+    assert_eq!(map.grid[5][6], Tile::LargeScientific);
+}
+
+#[test]
+fn test_crews_are_only_sent_when_complete() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][10] = Tile::LargeScientific;
+    let mut crews = Crews::new(Cooperation { large_minerals: 0, large_science: 1 }, 3);
+    let mut robots = vec![
+        robot(0, RobotType::Miner, 2, 5),
+        robot(1, RobotType::Miner, 8, 5),
+        robot(2, RobotType::Explorer, 9, 5),
+    ];
+
+    crews.recruit(&map, &mut robots);
+    assert!(robots.iter().all(|r| r.task == Task::Unmanaged));
+
+    robots[2] = robot(2, RobotType::Scientist, 9, 5);
+    crews.recruit(&map, &mut robots);
+    assert_eq!(robots[0].task, Task::Unmanaged);
+    assert_eq!(robots[1].task, Task::Assigned(10, 5));
+    assert_eq!(robots[2].task, Task::Assigned(10, 5));
+    assert_eq!(crews.site(1), Some((10, 5)));
+}
+
+#[test]
+fn test_full_crew_extracts_site() {
+    let mut map = empty_map(20, 10);
+    map.grid[5][10] = Tile::LargeMineral;
+    let mut crews = Crews::new(Cooperation { large_minerals: 1, large_science: 0 }, 2);
+    let mut robots = vec![robot(0, RobotType::Miner, 10, 5), robot(1, RobotType::Miner, 11, 5)];
+
+    crews.recruit(&map, &mut robots);
+    assert!(crews.extract(&mut map, &mut robots).is_empty());
+
+    robots[1].x = 10;
+    let events = crews.extract(&mut map, &mut robots);
+
+    assert_eq!(map.grid[5][10], Tile::Empty);
+    assert_eq!(crews.completed, 1);
+    assert!(robots.iter().all(|r| r.discoveries == vec![(10, 5, Tile::Mineral)]));
+    assert!(events.contains(&Event::SiteExtracted { x: 10, y: 5, tile: Tile::LargeMineral, robots: vec![0, 1] }));
+}
+
+#[test]
+fn test_miners_rendezvous_at_deposit() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Fixed { explorers: 0, miners: 2, scientists: 0 },
+        cooperation: Cooperation { large_minerals: 1, large_science: 0 },
+        ..SimulationConfig::new(42, 20, 10)
+    });
+    for row in simulation.map.grid.iter_mut() {
+        for tile in row.iter_mut().filter(|t| **t != Tile::Station) {
+            *tile = Tile::Empty;
+        }
+    }
+    simulation.map.grid[2][6] = Tile::LargeMineral;
+    for robot in simulation.robots.iter_mut() {
+        robot.energy = 50;
+    }
+
+    for _ in 0..20 {
+        simulation.step();
+    }
+
+    let carried: usize = simulation.robots.iter().map(|r| r.discoveries.len()).sum();
+    assert_eq!(simulation.crews.completed, 1);
+    assert_eq!(simulation.delivered(Tile::Mineral) + carried, 2);
+}
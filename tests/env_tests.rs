@@ -0,0 +1,91 @@
+use robot_swarm::env::{Env, Rewards};
+use robot_swarm::map::Tile;
+use robot_swarm::protocol::Episode;
+use robot_swarm::robot::Command;
+
+fn episode() -> Episode {
+    Episode { seed: 7, width: 20, height: 10, robots: 4, max_ticks: 200 }
+}
+
+fn nothing() -> Rewards {
+    Rewards { energy: 0.0, mineral: 0.0, scientific: 0.0, energy_used: 0.0, tick: 0.0 }
+}
+
+#[test]
+fn test_reset_is_reproducible_per_seed() {
+    let mut env = Env::new(episode(), Rewards::default()).unwrap();
+
+    let first = serde_json::to_string(&env.reset(3).unwrap()).unwrap();
+    env.step(&[]).unwrap();
+    let again = serde_json::to_string(&env.reset(3).unwrap()).unwrap();
+
+    assert_eq!(first, again);
+    assert_eq!(env.simulation().tick, 0);
+    assert_eq!(env.episode.seed, 3);
+}
+
+#[test]
+fn test_delivery_rewards_add_up_to_the_station_totals() {
+    let rewards = Rewards { energy: 1.0, mineral: 10.0, scientific: 100.0, ..nothing() };
+    let mut env = Env::new(episode(), rewards).unwrap();
+
+    let mut total = 0.0;
+    let mut done = false;
+    while !done {
+        let (_, reward, finished) = env.step(&[]).unwrap();
+        total += reward;
+        done = finished;
+    }
+
+    let simulation = env.simulation();
+    let expected = simulation.delivered(Tile::Energy) as f64
+        + 10.0 * simulation.delivered(Tile::Mineral) as f64
+        + 100.0 * simulation.delivered(Tile::Scientific) as f64;
+    assert_eq!(total, expected);
+}
+
+#[test]
+fn test_energy_use_is_penalised() {
+    let mut env = Env::new(episode(), Rewards { energy_used: -1.0, ..nothing() }).unwrap();
+
+    let total: f64 = (0..20).map(|_| env.step(&[]).unwrap().1).sum();
+
+    let used: usize = env.simulation().robots.iter().map(|r| r.energy_used).sum();
+    assert!(used > 0);
+    assert_eq!(total, -(used as f64));
+}
+
+#[test]
+fn test_staying_robots_spend_nothing() {
+    let mut env = Env::new(episode(), Rewards { energy_used: -1.0, ..nothing() }).unwrap();
+    let actions: Vec<(usize, Command)> = (0..4).map(|robot| (robot, Command::Stay)).collect();
+
+    let (observation, reward, _) = env.step(&actions).unwrap();
+
+    assert_eq!(reward, 0.0);
+    assert_eq!(observation.tick, 1);
+    assert!(observation.robots.iter().all(|robot| (robot.x, robot.y) == (0, 5)));
+}
+
+#[test]
+fn test_episode_ends_at_max_ticks() {
+    let mut env = Env::new(Episode { max_ticks: 3, ..episode() }, Rewards { tick: -1.0, ..nothing() }).unwrap();
+
+    let steps: Vec<(f64, bool)> = (0..4).map(|_| {
+        let (_, reward, done) = env.step(&[]).unwrap();
+        (reward, done)
+    }).collect();
+
+    assert_eq!(steps, [(-1.0, false), (-1.0, false), (-1.0, true), (0.0, true)]);
+    assert_eq!(env.simulation().tick, 3);
+}
+
+#[test]
+fn test_bad_input_is_an_error() {
+    assert!(Env::new(Episode { height: 0, ..episode() }, Rewards::default()).is_err());
+    assert!(Env::new(Episode { robots: 0, ..episode() }, Rewards::default()).is_err());
+
+    let mut env = Env::new(Episode { height: 4, ..episode() }, Rewards::default()).unwrap();
+    assert!(env.step(&[(9, Command::Stay)]).is_err());
+    assert_eq!(env.simulation().tick, 0);
+}
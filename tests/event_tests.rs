@@ -0,0 +1,119 @@
+use robot_swarm::cooperation::Cooperation;
+use robot_swarm::event::{read_log, Event, EventLog, Record};
+use robot_swarm::map::Tile;
+use robot_swarm::replay::Replay;
+use robot_swarm::simulation::{Simulation, SimulationConfig, Swarm};
+
+#[test]
+fn test_event_log_round_trip() {
+    let records = vec![
+        Record {
+            tick: 1,
+            event: Event::RobotMoved { robot: 0, from: (0, 5), to: (1, 5) },
+        },
+        Record {
+            tick: 2,
+            event: Event::ResourceHarvested { robot: 0, x: 1, y: 5, tile: Tile::Mineral },
+        },
+    ];
+
+    let mut buffer = Vec::new();
+    EventLog::new(&mut buffer).write_all(&records).unwrap();
+
+    let text = String::from_utf8(buffer.clone()).unwrap();
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.contains("\"event\":\"RobotMoved\""));
+
+    let parsed = read_log(buffer.as_slice()).unwrap();
+    assert_eq!(parsed, records);
+}
+
+#[test]
+fn test_simulation_announces_run_and_robots() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let events = simulation.take_events();
+
+    assert!(matches!(events[0].event, Event::RunStarted { seed: 42, width: 20, height: 10, .. }));
+    let built = events
+        .iter()
+        .filter(|r| matches!(r.event, Event::RobotBuilt { .. }))
+        .count();
+    assert_eq!(built, 3);
+    assert!(events.iter().all(|r| r.tick == 0));
+}
+
+#[test]
+fn test_replay_reconstructs_final_state() {
+    let mut simulation = Simulation::new(7, 20, 10, 3);
+    let mut records = simulation.take_events();
+
+    for _ in 0..40 {
+        simulation.step();
+        records.extend(simulation.take_events());
+    }
+
+    let mut replay = Replay::new(records).unwrap();
+    let last_tick = replay.last_tick();
+    let replayed = replay.seek(last_tick);
+
+    assert_eq!(replayed.map.grid, simulation.map.grid);
+    for (replayed, robot) in replayed.robots.iter().zip(&simulation.robots) {
+        assert_eq!((replayed.x, replayed.y), (robot.x, robot.y));
+    }
+    assert_eq!(replayed.stations.len(), simulation.stations.len());
+    assert_eq!(replayed.delivered(Tile::Mineral), simulation.delivered(Tile::Mineral));
+}
+
+#[test]
+fn test_replay_seeks_back_and_forth() {
+    let mut simulation = Simulation::new(7, 20, 10, 3);
+    let mut records = simulation.take_events();
+    let mut positions = Vec::new();
+    for _ in 0..30 {
+        simulation.step();
+        records.extend(simulation.take_events());
+        positions.push(simulation.robots.iter().map(|r| (r.x, r.y)).collect::<Vec<_>>());
+    }
+    let mut replay = Replay::new(records).unwrap();
+
+    for tick in [30, 12, 13, 25, 1] {
+        let replayed = replay.seek(tick);
+        let at: Vec<_> = replayed.robots.iter().map(|r| (r.x, r.y)).collect();
+        assert_eq!(replayed.tick, tick);
+        assert_eq!(at, positions[tick as usize - 1]);
+    }
+}
+
+#[test]
+fn test_replay_restores_large_sites_from_a_saved_log() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Random(6),
+        cooperation: Cooperation { large_minerals: 3, large_science: 2 },
+        ..SimulationConfig::new(7, 30, 12)
+    });
+    let start = simulation.map.grid.clone();
+    let mut records = simulation.take_events();
+    for _ in 0..60 {
+        simulation.step();
+        records.extend(simulation.take_events());
+    }
+    let mut buffer = Vec::new();
+    EventLog::new(&mut buffer).write_all(&records).unwrap();
+
+    let mut replay = Replay::new(read_log(buffer.as_slice()).unwrap()).unwrap();
+
+    assert!(start.iter().flatten().any(|&tile| tile == Tile::LargeMineral));
+    assert_eq!(replay.seek(0).map.grid, start);
+    let last_tick = replay.last_tick();
+    assert_eq!(replay.seek(last_tick).map.grid, simulation.map.grid);
+}
+
+#[test]
+fn test_replay_requires_run_started() {
+    let records = vec![Record {
+        tick: 1,
+        event: Event::RobotStranded { robot: 0, x: 1, y: 1 },
+    }];
+
+    assert!(Replay::new(records).is_none());
+}
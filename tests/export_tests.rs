@@ -0,0 +1,74 @@
+use robot_swarm::export::{render_frame, render_heatmap, write_png, GifWriter, Image, Trails, PALETTE};
+use robot_swarm::heatmap::Heatmap;
+use robot_swarm::map::Tile;
+use robot_swarm::simulation::Simulation;
+
+#[test]
+fn test_render_frame_scales_map() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let trails = Trails::new();
+
+    let image = render_frame(&simulation.map, &simulation.robots, &trails, 4);
+
+    assert_eq!((image.width, image.height), (80, 40));
+    assert_eq!(image.pixels.len(), 80 * 40);
+}
+
+#[test]
+fn test_render_frame_colours_tiles_and_robots() {
+    let mut simulation = Simulation::new(42, 20, 10, 1);
+    simulation.map.grid[0][19] = Tile::Obstacle;
+    let trails = Trails::new();
+
+    let image = render_frame(&simulation.map, &simulation.robots, &trails, 2);
+
+    assert_eq!(image.rgb(39, 1), PALETTE[1]);
+    let robot = &simulation.robots[0];
+    let robot_pixel = image.get(robot.x * 2, robot.y * 2);
+    assert!(image.get(38, 0) != robot_pixel);
+}
+
+#[test]
+fn test_trails_remember_visited_cells() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let mut trails = Trails::new();
+
+    trails.record(&simulation.robots);
+    simulation.step();
+    trails.record(&simulation.robots);
+
+    assert!(trails.contains(0, 5));
+    for robot in &simulation.robots {
+        assert!(trails.contains(robot.x, robot.y));
+    }
+}
+
+#[test]
+fn test_heatmap_marks_hot_cells() {
+    let simulation = Simulation::new(42, 4, 6, 0);
+    let mut heatmap = Heatmap::new(4, 6);
+    heatmap.add(0, 0, 10);
+    heatmap.add(1, 0, 1);
+
+    let image = render_heatmap(&simulation.map, &heatmap, 1);
+
+    assert_eq!(image.get(0, 0), 31);
+    assert!(image.get(1, 0) > 16 && image.get(1, 0) < 31);
+}
+
+#[test]
+fn test_png_and_gif_encoding() {
+    let image = Image::new(8, 4);
+
+    let mut png = Vec::new();
+    write_png(&mut png, &image).unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+
+    let mut gif = Vec::new();
+    {
+        let mut writer = GifWriter::new(&mut gif, 8, 4).unwrap();
+        writer.add_frame(&image, 10).unwrap();
+        writer.add_frame(&image, 10).unwrap();
+    }
+    assert_eq!(&gif[0..6], b"GIF89a");
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use robot_swarm::event::Event;
+use robot_swarm::map::Tile;
+use robot_swarm::robot::{Robot, RobotType, Strategy};
+use robot_swarm::simulation::{faction_home, Faction, Simulation, SimulationConfig, Swarm, MAX_FACTIONS};
+use common::empty_map;
+
+fn rivals(count: usize, strategy: Strategy) -> Vec<Faction> {
+    vec![Faction { swarm: Swarm::Random(3), strategy }; count]
+}
+
+#[test]
+fn test_each_faction_starts_at_its_own_home() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        rivals: rivals(1, Strategy::Greedy),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    assert_eq!(simulation.factions, 2);
+    assert_eq!(simulation.robots.len(), 6);
+    for robot in &simulation.robots {
+        assert_eq!((robot.x, robot.y), faction_home(robot.faction, 20, 10));
+    }
+    assert_eq!(simulation.robots[3].strategy, Strategy::Greedy);
+    assert_eq!(simulation.stations[1].faction, 1);
+    assert_eq!(simulation.map.grid[5][19], Tile::Station);
+
+    let events = simulation.take_events();
+    assert!(events.iter().any(|r| matches!(r.event, Event::RobotBuilt { robot: 5, faction: 1, .. })));
+}
+
+#[test]
+fn test_rivals_get_the_same_swarm_and_are_capped() {
+    let simulation = Simulation::from_config(SimulationConfig {
+        rivals: rivals(6, Strategy::Standard),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    assert_eq!(simulation.factions, MAX_FACTIONS);
+    let types = |faction| -> Vec<RobotType> {
+        simulation.robots.iter().filter(|r| r.faction == faction).map(|r| r.robot_type).collect()
+    };
+    assert_eq!(types(0), types(3));
+}
+
+#[test]
+fn test_robots_only_return_to_own_stations() {
+    let simulation = Simulation::from_config(SimulationConfig {
+        rivals: rivals(1, Strategy::Standard),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    assert_eq!(simulation.nearest_station(0, 18, 5), 0);
+    assert_eq!(simulation.nearest_station(1, 1, 5), 1);
+}
+
+#[test]
+fn test_scores_are_kept_per_faction() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        rivals: rivals(1, Strategy::Standard),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+    simulation.stations[0].deliver((3, 3, Tile::Mineral));
+    simulation.stations[1].deliver((4, 4, Tile::Mineral));
+    simulation.stations[1].deliver((5, 5, Tile::Scientific));
+
+    assert_eq!(simulation.score(0), 1);
+    assert_eq!(simulation.score(1), 2);
+    assert_eq!(simulation.delivered_by(1, Tile::Scientific), 1);
+    assert_eq!(simulation.delivered(Tile::Mineral), 2);
+}
+
+#[test]
+fn test_greedy_explorer_heads_for_resources() {
+    let mut map = empty_map(10, 10);
+    map.grid[2][7] = Tile::Scientific;
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut robot = Robot::build(0, RobotType::Explorer, 2, 2);
+    robot.strategy = Strategy::Greedy;
+    robot.energy = 20;
+    for _ in 0..5 {
+        robot.move_robot(&map, &mut rng, 0, 5);
+    }
+
+    assert_eq!((robot.x, robot.y), (7, 2));
+    assert!("greedy".parse::<Strategy>().is_ok());
+    assert!("lazy".parse::<Strategy>().is_err());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use robot_swarm::frontier::{Exploration, Frontiers, SENSOR_RANGE};
+use robot_swarm::map::Tile;
+use robot_swarm::metrics::coverage;
+use robot_swarm::robot::{Robot, RobotType};
+use robot_swarm::simulation::{Simulation, SimulationConfig, Swarm};
+use common::empty_map;
+
+#[test]
+fn test_random_exploration_plans_nothing() {
+    let map = empty_map(10, 10);
+    let mut frontiers = Frontiers::new(Exploration::Random, 10, 10, 1, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Explorer, 5, 5)];
+
+    frontiers.tick(&map, &mut robots);
+
+    assert_eq!("frontier".parse(), Ok(Exploration::Frontier));
+    assert!(!frontiers.is_known(0, 5, 5));
+    assert_eq!(robots[0].waypoint, None);
+}
+
+#[test]
+fn test_robots_reveal_cells_in_sensor_range() {
+    let map = empty_map(10, 10);
+    let mut frontiers = Frontiers::new(Exploration::Frontier, 10, 10, 1, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Miner, 5, 5)];
+
+    frontiers.tick(&map, &mut robots);
+
+    assert!(frontiers.is_known(0, 5 + SENSOR_RANGE, 5 - SENSOR_RANGE));
+    assert!(!frontiers.is_known(0, 5 + SENSOR_RANGE + 1, 5));
+    assert!(frontiers.is_frontier(&map, 0, 5 + SENSOR_RANGE, 5));
+    assert!(!frontiers.is_frontier(&map, 0, 5, 5));
+    assert_eq!(frontiers.known_percent(0), 25.0);
+}
+
+#[test]
+fn test_explorer_heads_for_nearest_frontier() {
+    let mut map = empty_map(20, 10);
+    for y in 0..10 {
+        map.grid[y][0] = Tile::Obstacle;
+    }
+    map.grid[0][0] = Tile::Empty;
+    let mut frontiers = Frontiers::new(Exploration::Frontier, 20, 10, 1, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Explorer, 0, 0)];
+
+    frontiers.tick(&map, &mut robots);
+
+    // Straight on along the top row; the obstacle column rules out going down
+    assert_eq!(frontiers.target(0), Some((SENSOR_RANGE, 0)));
+    assert_eq!(robots[0].waypoint, Some((1, 0)));
+}
+
+#[test]
+fn test_explorers_split_frontiers() {
+    let map = empty_map(30, 10);
+    let mut frontiers = Frontiers::new(Exploration::Frontier, 30, 10, 1, 2);
+    let mut robots = vec![
+        Robot::build(0, RobotType::Explorer, 15, 5),
+        Robot::build(1, RobotType::Explorer, 15, 5),
+    ];
+
+    frontiers.tick(&map, &mut robots);
+
+    let (first, second) = (frontiers.target(0).unwrap(), frontiers.target(1).unwrap());
+    assert!(first.0.abs_diff(second.0).max(first.1.abs_diff(second.1)) > SENSOR_RANGE);
+    assert_ne!(robots[0].waypoint, robots[1].waypoint);
+}
+
+#[test]
+fn test_frontier_exploration_covers_more_ground() {
+    let covered = |exploration| {
+        let mut simulation = Simulation::from_config(SimulationConfig {
+            swarm: Swarm::Fixed { explorers: 3, miners: 0, scientists: 0 },
+            exploration,
+            ..SimulationConfig::new(7, 40, 20)
+        });
+        for robot in simulation.robots.iter_mut() {
+            robot.energy = 10_000;
+        }
+        for _ in 0..150 {
+            simulation.step();
+        }
+        coverage(&simulation)
+    };
+
+    assert!(covered(Exploration::Frontier) > covered(Exploration::Random) * 2.0);
+}
@@ -0,0 +1,63 @@
+use robot_swarm::heatmap::{Heatmap, Overlay};
+use robot_swarm::simulation::Simulation;
+
+#[test]
+fn test_heatmap_add_and_get() {
+    let mut heatmap = Heatmap::new(3, 2);
+    heatmap.add(2, 1, 4);
+    heatmap.add(2, 1, 1);
+
+    assert_eq!(heatmap.get(2, 1), 5);
+    assert_eq!(heatmap.get(0, 0), 0);
+    assert_eq!(heatmap.max(), 5);
+}
+
+#[test]
+fn test_block_sum_clips_to_grid() {
+    let mut heatmap = Heatmap::new(4, 4);
+    for y in 0..4 {
+        for x in 0..4 {
+            heatmap.add(x, y, 1);
+        }
+    }
+
+    assert_eq!(heatmap.block_sum(0, 0, 2, 2), 4);
+    assert_eq!(heatmap.block_sum(3, 3, 4, 4), 1);
+    assert_eq!(heatmap.block_sum(8, 0, 2, 2), 0);
+}
+
+#[test]
+fn test_to_csv_is_one_line_per_row() {
+    let mut heatmap = Heatmap::new(3, 2);
+    heatmap.add(1, 0, 7);
+
+    assert_eq!(heatmap.to_csv(), "0,7,0\n0,0,0\n");
+}
+
+#[test]
+fn test_simulation_records_visits_and_energy() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    for _ in 0..10 {
+        simulation.step();
+    }
+
+    let visits: u32 = simulation.heatmaps.visits.values().iter().sum();
+    assert_eq!(visits as usize, simulation.robots.len() * 11);
+
+    let energy: u32 = simulation.heatmaps.energy.values().iter().sum();
+    let used: usize = simulation.robots.iter().map(|r| r.energy_used).sum();
+    assert_eq!(energy as usize, used);
+}
+
+#[test]
+fn test_overlay_cycles_through_heatmaps() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let (heatmaps, pheromones) = (&simulation.heatmaps, &simulation.pheromones);
+
+    assert_eq!(Overlay::default().select(heatmaps, pheromones), None);
+    assert_eq!(Overlay::None.next().select(heatmaps, pheromones), Some(&heatmaps.visits));
+    assert_eq!(Overlay::Visits.next().select(heatmaps, pheromones), Some(&heatmaps.energy));
+    assert_eq!(Overlay::Energy.next().select(heatmaps, pheromones), Some(&pheromones.trail));
+    assert_eq!(Overlay::Trail.next().select(heatmaps, pheromones), Some(&pheromones.explored));
+    assert_eq!(Overlay::Explored.next(), Overlay::None);
+}
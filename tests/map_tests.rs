@@ -1,4 +1,7 @@
+mod common;
+
 use robot_swarm::map::{Map, Tile};
+use common::empty_map;
 
 #[test]
 fn test_map_creation_with_fixed_seed() {
@@ -95,4 +98,30 @@ fn test_tile_enum() {
     set.insert(scientific);
     set.insert(station);
     assert_eq!(set.len(), 6);
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_distances_walk_around_obstacles() {
+    let mut map = empty_map(5, 6);
+    // A wall down column 2 with a gap at the bottom
+    for y in 0..5 {
+        map.grid[y][2] = Tile::Obstacle;
+    }
+
+    let distances = map.distances_from(0, 0);
+
+    assert_eq!(distances[0], 0);
+    assert_eq!(distances[2], usize::MAX);
+    assert_eq!(distances[4], 5 + 4 + 5);
+}
+
+#[test]
+fn test_station_sits_at_the_left_centre_of_small_maps() {
+    let map = Map::new(42, 10, 4);
+
+    let stations: Vec<(usize, usize)> = (0..map.height)
+        .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+        .filter(|&(x, y)| map.grid[y][x] == Tile::Station)
+        .collect();
+    assert_eq!(stations, [(0, 2)]);
+}
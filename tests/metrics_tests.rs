@@ -0,0 +1,86 @@
+use robot_swarm::event::{Event, Record};
+use robot_swarm::map::Tile;
+use robot_swarm::metrics::Metrics;
+use robot_swarm::simulation::Simulation;
+
+fn run(seed: u32, ticks: usize) -> (Simulation, Metrics) {
+    let mut simulation = Simulation::new(seed, 20, 10, 3);
+    simulation.take_events();
+    let mut metrics = Metrics::new(&simulation);
+
+    for _ in 0..ticks {
+        simulation.step();
+        let events = simulation.take_events();
+        metrics.record(&simulation, &events);
+    }
+
+    (simulation, metrics)
+}
+
+#[test]
+fn test_metrics_track_every_tick() {
+    let (simulation, metrics) = run(42, 25);
+
+    assert_eq!(metrics.per_tick().len(), 25);
+    assert_eq!(metrics.report().ticks, simulation.tick);
+
+    for stats in metrics.robots() {
+        assert_eq!(stats.distance as u64 + stats.idle_ticks, 25);
+    }
+}
+
+#[test]
+fn test_metrics_energy_matches_robots() {
+    let (simulation, metrics) = run(7, 30);
+
+    for (stats, robot) in metrics.robots().iter().zip(&simulation.robots) {
+        assert_eq!(stats.energy_used, robot.energy_used);
+    }
+}
+
+#[test]
+fn test_metrics_count_harvest_and_delivery() {
+    let simulation = Simulation::new(1, 20, 10, 1);
+    let mut metrics = Metrics::new(&simulation);
+
+    let records = vec![
+        Record {
+            tick: 4,
+            event: Event::ResourceHarvested { robot: 0, x: 2, y: 3, tile: Tile::Energy },
+        },
+        Record {
+            tick: 4,
+            event: Event::DiscoveryDelivered { robot: 0, x: 2, y: 3, tile: Tile::Energy },
+        },
+    ];
+    metrics.record(&simulation, &records);
+
+    let report = metrics.report();
+    assert_eq!(report.first_discovery, Some(4));
+    assert_eq!(report.resources_delivered, 1);
+    assert_eq!(report.robots[0].resources_harvested, 1);
+}
+
+#[test]
+fn test_metrics_coverage_grows() {
+    let (_, metrics) = run(3, 40);
+    let ticks = metrics.per_tick();
+
+    assert!(ticks.windows(2).all(|w| w[0].coverage <= w[1].coverage));
+    assert!(metrics.coverage() > 0.0 && metrics.coverage() <= 100.0);
+}
+
+#[test]
+fn test_metrics_exports() {
+    let (_, metrics) = run(42, 10);
+
+    let robots_csv = metrics.robots_csv();
+    assert_eq!(robots_csv.lines().count(), 4);
+    assert!(robots_csv.starts_with("robot,robot_type,distance"));
+
+    assert_eq!(metrics.ticks_csv().lines().count(), 11);
+
+    let json: serde_json::Value = serde_json::from_str(&metrics.to_json()).unwrap();
+    assert_eq!(json["robots"].as_array().unwrap().len(), 3);
+    assert_eq!(json["per_tick"].as_array().unwrap().len(), 10);
+}
@@ -0,0 +1,78 @@
+use robot_swarm::map::Tile;
+use robot_swarm::metrics::{coverage, Metrics};
+use robot_swarm::mission::{Mission, MissionStatus, Objective};
+use robot_swarm::simulation::Simulation;
+
+#[test]
+fn test_parse_mission() {
+    let mission: Mission = "minerals=5, science=2,explore=40%,ticks=500".parse().unwrap();
+
+    assert_eq!(
+        mission.objectives,
+        vec![
+            Objective::Deliver { tile: Tile::Mineral, count: 5 },
+            Objective::Deliver { tile: Tile::Scientific, count: 2 },
+            Objective::Explore(40.0),
+        ]
+    );
+    assert_eq!(mission.tick_limit, Some(500));
+}
+
+#[test]
+fn test_parse_mission_rejects_bad_input() {
+    assert!("gold=3".parse::<Mission>().is_err());
+    assert!("minerals=lots".parse::<Mission>().is_err());
+    assert!("minerals".parse::<Mission>().is_err());
+    assert!("ticks=500".parse::<Mission>().is_err());
+    assert!("".parse::<Mission>().is_err());
+}
+
+#[test]
+fn test_mission_accomplished_when_objectives_met() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    simulation.stations[0].deliver((3, 4, Tile::Mineral));
+    simulation.stations[0].deliver((6, 1, Tile::Mineral));
+
+    let mission: Mission = "minerals=2,ticks=0".parse().unwrap();
+    assert_eq!(mission.evaluate(&simulation), MissionStatus::Accomplished);
+
+    let mission: Mission = "minerals=3".parse().unwrap();
+    assert_eq!(mission.evaluate(&simulation), MissionStatus::InProgress);
+}
+
+#[test]
+fn test_mission_fails_at_tick_limit() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    simulation.mission = Some("science=1000,ticks=5".parse().unwrap());
+
+    let mut ticks = 0;
+    while !simulation.is_finished() {
+        simulation.step();
+        ticks += 1;
+    }
+
+    assert_eq!(ticks, 5);
+    match simulation.mission_status() {
+        Some(MissionStatus::Failed(reason)) => assert!(reason.contains("analyse 1000 scientific sites")),
+        other => panic!("expected failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_coverage_grows_as_robots_move() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let mut metrics = Metrics::new(&simulation);
+    let start = coverage(&simulation);
+    assert!(start > 0.0);
+
+    for _ in 0..20 {
+        simulation.step();
+        let events = simulation.take_events();
+        metrics.record(&simulation, &events);
+    }
+
+    assert!(coverage(&simulation) > start);
+    assert!(coverage(&simulation) <= 100.0);
+    // The explore objective and the report agree
+    assert_eq!(Objective::Explore(50.0).progress(&simulation).0, metrics.coverage());
+}
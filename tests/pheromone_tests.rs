@@ -0,0 +1,96 @@
+mod common;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use robot_swarm::map::Tile;
+use robot_swarm::pheromone::{Pheromones, Stigmergy};
+use robot_swarm::robot::{Robot, RobotType, Strategy};
+use robot_swarm::simulation::{Simulation, SimulationConfig};
+use common::empty_map;
+
+fn ant(x: usize, y: usize) -> Robot {
+    let mut robot = Robot::build(0, RobotType::Miner, x, y);
+    robot.strategy = Strategy::Ant;
+    robot
+}
+
+#[test]
+fn test_robots_lay_trail_or_explored_scent() {
+    let mut pheromones = Pheromones::new(Stigmergy::default(), 10, 10);
+    let mut carrier = ant(2, 2);
+    carrier.returning = true;
+    carrier.discoveries.push((5, 5, Tile::Mineral));
+    let searcher = ant(7, 7);
+
+    pheromones.tick(&[carrier, searcher]);
+
+    assert_eq!(pheromones.trail.get(2, 2), 100);
+    assert_eq!(pheromones.explored.get(2, 2), 0);
+    assert_eq!(pheromones.explored.get(7, 7), 100);
+    assert!(pheromones.attraction(2, 2) > pheromones.attraction(0, 0));
+    assert!(pheromones.attraction(7, 7) < pheromones.attraction(0, 0));
+}
+
+#[test]
+fn test_pheromones_evaporate() {
+    let mut pheromones = Pheromones::new(Stigmergy { deposit: 100, evaporation: 0.5 }, 10, 10);
+    pheromones.trail.add(3, 3, 100);
+
+    pheromones.tick(&[]);
+    assert_eq!(pheromones.trail.get(3, 3), 50);
+
+    for _ in 0..10 {
+        pheromones.tick(&[]);
+    }
+    assert_eq!(pheromones.trail.get(3, 3), 0);
+}
+
+#[test]
+fn test_ant_takes_neighbouring_resource() {
+    let mut map = empty_map(10, 10);
+    map.grid[5][6] = Tile::Mineral;
+    map.grid[0][9] = Tile::Mineral;
+    let pheromones = Pheromones::new(Stigmergy::default(), 10, 10);
+    let mut robot = ant(5, 5);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    robot.navigate(&map, Some(&pheromones), &map.distances_from(0, 5), &mut rng, 0, 5);
+
+    assert_eq!((robot.x, robot.y), (6, 5));
+}
+
+#[test]
+fn test_ant_follows_the_trail() {
+    let map = empty_map(10, 10);
+    let mut pheromones = Pheromones::new(Stigmergy::default(), 10, 10);
+    pheromones.trail.add(5, 4, 100_000);
+    for (x, y) in [(4, 5), (6, 5), (5, 6)] {
+        pheromones.explored.add(x, y, 100_000);
+    }
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut followed = 0;
+    for _ in 0..20 {
+        let mut robot = ant(5, 5);
+        robot.navigate(&map, Some(&pheromones), &map.distances_from(0, 5), &mut rng, 0, 5);
+        if (robot.x, robot.y) == (5, 4) {
+            followed += 1;
+        }
+    }
+    assert_eq!(followed, 20);
+}
+
+#[test]
+fn test_ant_colony_is_reproducible_and_leaves_scent() {
+    let config = SimulationConfig { strategy: Strategy::Ant, ..SimulationConfig::new(42, 30, 15) };
+    let mut first = Simulation::from_config(config.clone());
+    let mut second = Simulation::from_config(config);
+    for _ in 0..50 {
+        first.step();
+        second.step();
+    }
+
+    assert!(first.pheromones.explored.max() > 0);
+    let positions = |s: &Simulation| s.robots.iter().map(|r| (r.x, r.y)).collect::<Vec<_>>();
+    assert_eq!(positions(&first), positions(&second));
+}
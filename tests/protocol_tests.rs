@@ -0,0 +1,118 @@
+use robot_swarm::protocol::{serve, Episode, Request, Response, Session};
+use serde_json::Value;
+
+fn request(line: &str) -> Request {
+    serde_json::from_str(line).unwrap()
+}
+
+fn observation(response: Response) -> robot_swarm::protocol::Observation {
+    match response {
+        Response::Observation(observation) | Response::Step { observation, .. } => observation,
+        other => panic!("expected an observation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_step_needs_a_reset_first() {
+    let mut session = Session::new(Episode::default());
+
+    let response = session.handle(request(r#"{"cmd":"step"}"#));
+
+    assert!(matches!(response, Response::Error { .. }));
+}
+
+#[test]
+fn test_reset_starts_an_episode_with_the_requested_settings() {
+    let mut session = Session::new(Episode::default());
+
+    let first = observation(session.handle(request(r#"{"cmd":"reset","seed":7,"width":30,"height":12,"robots":5}"#)));
+
+    assert_eq!(first.tick, 0);
+    assert!(!first.done);
+    assert_eq!(first.robots.len(), 5);
+    assert_eq!(session.episode, Episode { seed: 7, width: 30, height: 12, robots: 5, ..Episode::default() });
+    assert!(matches!(
+        session.handle(request(r#"{"cmd":"reset","height":0}"#)),
+        Response::Error { .. }
+    ));
+    assert!(matches!(
+        session.handle(request(r#"{"cmd":"reset","robots":0}"#)),
+        Response::Error { .. }
+    ));
+    assert_eq!(session.episode.robots, 5);
+    assert_eq!(observation(session.handle(request(r#"{"cmd":"reset","width":1,"height":1}"#))).robots.len(), 5);
+}
+
+#[test]
+fn test_actions_steer_robots() {
+    let mut session = Session::new(Episode::default());
+    let start = observation(session.handle(request(r#"{"cmd":"reset","seed":7}"#)));
+    let (x, y) = (start.robots[0].x, start.robots[0].y);
+
+    let next = observation(session.handle(request(r#"{"cmd":"step","actions":[{"robot":0,"action":"stay"}]}"#)));
+
+    assert_eq!(next.tick, 1);
+    assert_eq!((next.robots[0].x, next.robots[0].y), (x, y));
+    assert_eq!(next.robots[0].energy, start.robots[0].energy);
+}
+
+#[test]
+fn test_invalid_actions_leave_the_episode_untouched() {
+    let mut session = Session::new(Episode::default());
+    session.handle(request(r#"{"cmd":"reset"}"#));
+
+    let unknown_robot = session.handle(request(r#"{"cmd":"step","actions":[{"robot":99,"action":"east"}]}"#));
+    let unknown_action = session.handle(request(r#"{"cmd":"step","actions":[{"robot":0,"action":"jump"}]}"#));
+    let next = observation(session.handle(request(r#"{"cmd":"step"}"#)));
+
+    assert!(matches!(unknown_robot, Response::Error { .. }));
+    assert!(matches!(unknown_action, Response::Error { .. }));
+    assert_eq!(next.tick, 1);
+}
+
+#[test]
+fn test_serve_answers_each_line_until_closed() {
+    let input = concat!(
+        r#"{"cmd":"reset","max_ticks":1}"#,
+        "\nnot json\n",
+        r#"{"cmd":"step"}"#,
+        "\n",
+        r#"{"cmd":"step"}"#,
+        "\n",
+        r#"{"cmd":"close"}"#,
+        "\n",
+        r#"{"cmd":"step"}"#,
+        "\n"
+    );
+    let mut output = Vec::new();
+
+    serve(input.as_bytes(), &mut output, Episode::default()).unwrap();
+
+    let responses: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let types: Vec<&str> = responses.iter().map(|r| r["type"].as_str().unwrap()).collect();
+    assert_eq!(types, ["observation", "error", "step", "error", "closed"]);
+    assert_eq!(responses[2]["done"], true);
+    assert!(responses[2]["reward"].is_number());
+    assert!(responses[0].get("reward").is_none());
+}
+
+#[test]
+fn test_steps_report_the_reward() {
+    let mut session = Session::new(Episode::default());
+    session.handle(request(r#"{"cmd":"reset","seed":7}"#));
+
+    let response = session.handle(request(r#"{"cmd":"step"}"#));
+
+    let Response::Step { observation, reward } = response else {
+        panic!("expected a step, got {:?}", response);
+    };
+    let moved: usize = observation.robots.iter().filter(|robot| (robot.x, robot.y) != (0, 5)).count();
+    assert_eq!(observation.tick, 1);
+    assert!(!observation.done);
+    // Nothing can be delivered on the first tick, so only the default energy penalty applies
+    assert_eq!(reward, -0.01 * moved as f64);
+}
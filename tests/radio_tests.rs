@@ -0,0 +1,98 @@
+use robot_swarm::event::Event;
+use robot_swarm::radio::{Link, Network, Radio};
+use robot_swarm::robot::{Robot, RobotType};
+use robot_swarm::simulation::{Simulation, SimulationConfig};
+use robot_swarm::station::Station;
+
+fn robot_at(id: usize, x: usize, y: usize) -> Robot {
+    Robot::build(id, RobotType::Explorer, x, y)
+}
+
+fn radio(range: usize) -> Radio {
+    Radio { range, interval: 1, ..Radio::default() }
+}
+
+#[test]
+fn test_radio_is_off_by_default() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let mut events = Vec::new();
+    for _ in 0..50 {
+        simulation.step();
+        events.extend(simulation.take_events());
+    }
+
+    assert!(!events.iter().any(|r| matches!(r.event, Event::TelemetryReceived { .. })));
+    assert_eq!(simulation.network.in_contact(), 0);
+    assert!(simulation.network.heard(0).is_none());
+}
+
+#[test]
+fn test_robots_relay_for_each_other() {
+    let stations = [Station::new(0, 5)];
+    let robots = vec![robot_at(0, 3, 5), robot_at(1, 6, 5), robot_at(2, 15, 5)];
+    let mut network = Network::new(radio(3), robots.len(), 1);
+
+    network.tick(1, &robots, &stations);
+
+    assert_eq!(network.link(0), Some(Link { station: 0, hops: 1 }));
+    assert_eq!(network.link(1), Some(Link { station: 0, hops: 2 }));
+    assert_eq!(network.link(2), None);
+    assert_eq!(network.in_contact(), 2);
+}
+
+#[test]
+fn test_relay_beacons_extend_the_network() {
+    let stations = [Station::new(0, 5)];
+    let robots = vec![robot_at(0, 9, 5)];
+    let mut network = Network::new(Radio { relays: vec![(4, 5)], ..radio(5) }, 1, 1);
+
+    network.tick(1, &robots, &stations);
+
+    assert_eq!(network.link(0), Some(Link { station: 0, hops: 2 }));
+}
+
+#[test]
+fn test_telemetry_arrives_after_latency() {
+    let stations = [Station::new(0, 5)];
+    let robots = vec![robot_at(0, 2, 5), robot_at(1, 4, 5)];
+    let mut network = Network::new(Radio { latency: 2, ..radio(2) }, robots.len(), 1);
+
+    let events = network.tick(1, &robots, &stations);
+    assert!(events.is_empty());
+    let events = network.tick(3, &robots, &stations);
+
+    // Robot 0 is one hop away, robot 1 two hops and still in flight
+    assert!(events.contains(&Event::TelemetryReceived { robot: 0, station: 0, x: 2, y: 5, sent: 1, hops: 1 }));
+    assert!(!events.iter().any(|e| matches!(e, Event::TelemetryReceived { robot: 1, sent: 1, .. })));
+    assert_eq!(network.heard(0).map(|t| (t.x, t.y, t.sent)), Some((2, 5, 1)));
+    assert!(network.heard(1).is_none());
+}
+
+#[test]
+fn test_packet_loss_and_unreachable_reports() {
+    let stations = [Station::new(0, 5)];
+    let robots = vec![robot_at(0, 1, 5), robot_at(1, 15, 5)];
+    let mut network = Network::new(Radio { loss: 1.0, ..radio(2) }, robots.len(), 1);
+
+    let events = network.tick(1, &robots, &stations);
+
+    assert_eq!(events, vec![Event::MessageLost { robot: 0, hops: 1 }]);
+    assert_eq!((network.traffic.sent, network.traffic.lost, network.traffic.unreachable), (2, 1, 1));
+    assert_eq!(network.traffic.delivered, 0);
+}
+
+#[test]
+fn test_simulation_receives_live_telemetry() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        radio: Radio { range: 100, latency: 0, ..Radio::default() },
+        ..SimulationConfig::new(42, 20, 10)
+    });
+    for _ in 0..5 {
+        simulation.step();
+    }
+
+    for robot in &simulation.robots {
+        let telemetry = simulation.network.heard(robot.id).expect("robot should have reported");
+        assert_eq!((telemetry.x, telemetry.y, telemetry.sent), (robot.x, robot.y, 5));
+    }
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use robot_swarm::event::Event;
+use robot_swarm::reliability::{Maintenance, Reliability};
+use robot_swarm::robot::{Fault, Robot, RobotType, MAX_HEALTH};
+use robot_swarm::simulation::Simulation;
+use robot_swarm::station::Station;
+use robot_swarm::world::{Dynamics, Storm, World};
+use common::empty_map;
+
+#[test]
+fn test_robots_stay_healthy_by_default() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    for _ in 0..100 {
+        simulation.step();
+    }
+
+    assert!(simulation.robots.iter().all(|r| r.health == MAX_HEALTH && r.faults.is_empty()));
+}
+
+#[test]
+fn test_wear_only_applies_to_moves() {
+    let map = empty_map(10, 10);
+    let world = World::new(Dynamics::default(), &map, 1);
+    let mut maintenance = Maintenance::new(Reliability { wear: 1.0, ..Reliability::default() }, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Miner, 3, 3), Robot::build(1, RobotType::Miner, 4, 4)];
+
+    maintenance.tick(&mut robots, &[true, false], &[Station::new(0, 5)], &world);
+
+    assert_eq!(robots[0].health, MAX_HEALTH - 1);
+    assert_eq!(robots[1].health, MAX_HEALTH);
+}
+
+#[test]
+fn test_storm_damage_breaks_robots_down() {
+    let map = empty_map(10, 10);
+    let mut world = World::new(Dynamics { storms: 1, ..Dynamics::default() }, &map, 1);
+    world.storms[0] = Storm { x: 3, y: 3, dx: 1, dy: 0, radius: 1 };
+    let mut maintenance = Maintenance::new(Reliability { hazard_damage: 60, ..Reliability::default() }, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Explorer, 3, 3)];
+
+    assert!(maintenance.tick(&mut robots, &[false], &[], &world).is_empty());
+    let events = maintenance.tick(&mut robots, &[false], &[], &world);
+
+    assert!(robots[0].is_broken());
+    assert_eq!(events, vec![Event::RobotBrokeDown { robot: 0, x: 3, y: 3 }]);
+
+    let mut rng = StdRng::seed_from_u64(1);
+    robots[0].move_robot(&map, &mut rng, 0, 5);
+    assert_eq!((robots[0].x, robots[0].y), (3, 3));
+}
+
+#[test]
+fn test_station_repairs_docked_robots() {
+    let map = empty_map(10, 10);
+    let world = World::new(Dynamics::default(), &map, 1);
+    let mut maintenance = Maintenance::new(Reliability { repair_rate: 30, ..Reliability::default() }, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Scientist, 0, 5)];
+    robots[0].health = 50;
+    robots[0].faults.push(Fault::SensorDegraded);
+    let stations = [Station::new(0, 5)];
+
+    assert!(maintenance.tick(&mut robots, &[false], &stations, &world).is_empty());
+    assert!(robots[0].faults.is_empty());
+    assert_eq!(robots[0].health, 80);
+
+    // Waits at the station until the repair is done
+    let mut rng = StdRng::seed_from_u64(1);
+    robots[0].move_robot(&map, &mut rng, 0, 5);
+    assert_eq!((robots[0].x, robots[0].y), (0, 5));
+
+    let events = maintenance.tick(&mut robots, &[false], &stations, &world);
+    assert_eq!(robots[0].health, MAX_HEALTH);
+    assert_eq!(events, vec![Event::RobotRepaired { robot: 0, x: 0, y: 5 }]);
+}
+
+#[test]
+fn test_component_failures_slow_robots_down() {
+    let map = empty_map(10, 10);
+    let world = World::new(Dynamics::default(), &map, 1);
+    let mut maintenance = Maintenance::new(Reliability { failure_rate: 1.0, ..Reliability::default() }, 1);
+    let mut robots = vec![Robot::build(0, RobotType::Explorer, 5, 5)];
+
+    let events = maintenance.tick(&mut robots, &[false], &[], &world);
+    assert!(matches!(events[0], Event::ComponentFailed { robot: 0, .. }));
+
+    robots[0].faults = vec![Fault::MotorSlowed];
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut moves = 0;
+    for _ in 0..6 {
+        let before = (robots[0].x, robots[0].y);
+        robots[0].move_robot(&map, &mut rng, 0, 0);
+        if (robots[0].x, robots[0].y) != before {
+            moves += 1;
+        }
+    }
+    assert_eq!(moves, 3);
+}
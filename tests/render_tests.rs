@@ -0,0 +1,53 @@
+use crossterm::style::Color;
+use robot_swarm::render::{frame_lines, AsciiRenderer, NullRenderer, RenderMode, Renderer};
+use robot_swarm::simulation::Simulation;
+use robot_swarm::tui::{Frame, Status};
+use robot_swarm::viewport::Viewport;
+use std::time::Duration;
+
+fn status() -> Status {
+    Status::new(Duration::from_millis(400))
+}
+
+#[test]
+fn test_render_mode_parsing() {
+    assert_eq!("ansi".parse(), Ok(RenderMode::Ansi));
+    assert_eq!("no-emoji".parse(), Ok(RenderMode::NoEmoji));
+    assert_eq!("ascii".parse(), Ok(RenderMode::Ascii));
+    assert_eq!("none".parse(), Ok(RenderMode::Null));
+    assert!("fancy".parse::<RenderMode>().is_err());
+}
+
+#[test]
+fn test_ascii_renderer_is_plain_text() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let mut renderer = AsciiRenderer::with_size(Vec::new(), (100, 20));
+
+    renderer.draw(&simulation, &status(), &mut Viewport::default()).unwrap();
+    let output = String::from_utf8(renderer.into_inner()).unwrap();
+
+    assert!(output.starts_with("--- tick 0 ---"));
+    assert!(output.is_ascii());
+    assert!(!output.contains('\x1b'));
+    assert!(output.contains("Robots"));
+    // station at the left centre of the map
+    assert!(output.lines().nth(6).unwrap().starts_with('H'));
+}
+
+#[test]
+fn test_null_renderer_draws_nothing() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let mut renderer = NullRenderer;
+
+    assert!(renderer.draw(&simulation, &status(), &mut Viewport::default()).is_ok());
+    assert_eq!(renderer.poll_control(Duration::ZERO).unwrap(), None);
+}
+
+#[test]
+fn test_frame_lines_trims_blanks() {
+    let mut frame = Frame::new(10, 4);
+    frame.text(0, 0, "ab", Color::White);
+    frame.text(2, 1, "c", Color::White);
+
+    assert_eq!(frame_lines(&frame), vec!["ab".to_string(), "  c".to_string()]);
+}
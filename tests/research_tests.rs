@@ -0,0 +1,154 @@
+mod common;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use robot_swarm::event::{Event, Record};
+use robot_swarm::map::Tile;
+use robot_swarm::research::{equip, Labs, Research, Upgrade};
+use robot_swarm::robot::{Command, Robot, RobotType};
+use common::empty_map;
+
+fn scientist(id: usize, x: usize, y: usize, research: &Research) -> Robot {
+    let mut robot = Robot::build(id, RobotType::Scientist, x, y);
+    robot.energy = 20;
+    equip(research, &mut robot);
+    robot
+}
+
+fn delivered(robot: usize, x: usize, y: usize) -> Record {
+    Record { tick: 1, event: Event::DiscoveryDelivered { robot, x, y, tile: Tile::Scientific } }
+}
+
+#[test]
+fn test_analysis_is_instant_by_default() {
+    let mut map = empty_map(10, 10);
+    map.grid[3][3] = Tile::Scientific;
+    let mut robot = scientist(0, 3, 3, &Research::default());
+    robot.equipment.sensor_range = None;
+
+    robot.perform_action(&mut map);
+
+    assert!(!Research::default().is_enabled());
+    assert_eq!(robot.discoveries, vec![(3, 3, Tile::Scientific)]);
+}
+
+#[test]
+fn test_scientist_dwells_until_analysis_is_done() {
+    let mut map = empty_map(10, 10);
+    map.grid[3][3] = Tile::Scientific;
+    let research = Research { analysis_ticks: 3, ..Research::default() };
+    let mut robot = scientist(0, 3, 3, &research);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    robot.perform_action(&mut map);
+    for _ in 0..2 {
+        assert!(robot.is_analysing(&map));
+        robot.move_robot(&map, &mut rng, 0, 5);
+        assert_eq!((robot.x, robot.y, robot.energy), (3, 3, 20));
+        robot.perform_action(&mut map);
+    }
+
+    assert_eq!(robot.discoveries, vec![(3, 3, Tile::Scientific)]);
+    assert_eq!(map.grid[3][3], Tile::Empty);
+    assert!(!robot.is_analysing(&map));
+}
+
+#[test]
+fn test_moving_away_abandons_the_analysis() {
+    let mut map = empty_map(10, 10);
+    map.grid[3][3] = Tile::Scientific;
+    map.grid[3][4] = Tile::Scientific;
+    let research = Research { analysis_ticks: 3, ..Research::default() };
+    let mut robot = scientist(0, 3, 3, &research);
+    let mut rng = StdRng::seed_from_u64(1);
+    robot.perform_action(&mut map);
+
+    robot.command = Some(Command::Move(1, 0));
+    robot.move_robot(&map, &mut rng, 0, 5);
+    robot.perform_action(&mut map);
+
+    assert_eq!((robot.x, robot.y, robot.analysis), (4, 3, 1));
+    assert!(robot.discoveries.is_empty());
+}
+
+#[test]
+fn test_only_scientists_earn_research_points() {
+    let research = Research::preset();
+    let mut labs = Labs::new(research, 1, 42);
+    let mut robots = vec![scientist(0, 0, 5, &research), Robot::build(1, RobotType::Explorer, 0, 5)];
+
+    let events = labs.tick(&[delivered(0, 4, 4), delivered(1, 6, 6)], &mut robots);
+
+    let points = labs.value(4, 4);
+    assert!((1..=5).contains(&points));
+    assert_eq!(labs.value(4, 4), Labs::new(research, 1, 42).value(4, 4));
+    assert_eq!(labs.labs[0].points, points);
+    assert_eq!(events[0], Event::ResearchDelivered { robot: 0, points });
+}
+
+#[test]
+fn test_tech_tree_respects_costs_and_prerequisites() {
+    let research = Research { sensor_range: Some(4), ..Research::preset() };
+    let mut labs = Labs::new(research, 1, 42);
+    let mut robots = vec![scientist(0, 0, 5, &research)];
+    labs.labs[0].points = 10;
+
+    let events = labs.tick(&[], &mut robots);
+
+    assert_eq!(labs.labs[0].unlocked, vec![Upgrade::Batteries, Upgrade::Sensors]);
+    assert_eq!(labs.labs[0].next_upgrade(), Some((Upgrade::FastAnalysis, 10)));
+    assert_eq!(events.len(), 2);
+    assert_eq!(robots[0].equipment.battery, 10);
+    assert_eq!(robots[0].equipment.sensor_range, Some(8));
+
+    labs.labs[0].points += 45;
+    labs.tick(&[], &mut robots);
+    assert_eq!(labs.labs[0].next_upgrade(), None);
+    assert_eq!(robots[0].equipment.analysis_ticks, 3);
+    assert_eq!(robots[0].equipment.sensor_range, None);
+}
+
+#[test]
+fn test_sensor_range_limits_targeting() {
+    let mut map = empty_map(10, 10);
+    map.grid[9][9] = Tile::Scientific;
+    let research = Research { sensor_range: Some(2), ..Research::preset() };
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut near_sighted = scientist(0, 1, 1, &research);
+    let mut far_sighted = scientist(1, 1, 1, &research);
+    far_sighted.equipment.sensor_range = None;
+    near_sighted.move_robot(&map, &mut rng, 0, 5);
+    far_sighted.move_robot(&map, &mut rng, 0, 5);
+
+    assert_eq!((far_sighted.x, far_sighted.y), (2, 2));
+    assert_ne!((near_sighted.x, near_sighted.y), (2, 2));
+}
+
+#[test]
+fn test_equip_sets_the_configured_sensor_range() {
+    let unset = scientist(0, 1, 1, &Research { sensor_range: None, ..Research::preset() });
+    let preset = scientist(1, 1, 1, &Research::preset());
+
+    assert_eq!(unset.equipment.sensor_range, Robot::build(0, RobotType::Scientist, 1, 1).equipment.sensor_range);
+    assert_eq!(preset.equipment.sensor_range, Some(8));
+}
+
+#[test]
+fn test_bigger_sensors_widen_targeting() {
+    let mut map = empty_map(10, 10);
+    map.grid[5][6] = Tile::Scientific;
+    let research = Research::preset();
+    let mut labs = Labs::new(research, 1, 42);
+    let mut upgraded = vec![scientist(0, 0, 0, &research)];
+    let mut plain = scientist(1, 0, 0, &research);
+    labs.labs[0].points = 10;
+
+    labs.tick(&[], &mut upgraded);
+    upgraded[0].move_robot(&map, &mut StdRng::seed_from_u64(1), 0, 5);
+    plain.move_robot(&map, &mut StdRng::seed_from_u64(1), 0, 5);
+
+    assert!(labs.labs[0].unlocked.contains(&Upgrade::Sensors));
+    assert_eq!((upgraded[0].x, upgraded[0].y), (1, 1));
+    assert_ne!((plain.x, plain.y), (1, 1));
+}
@@ -1,7 +1,10 @@
+mod common;
+
 use robot_swarm::robot::{Robot, RobotType};
 use robot_swarm::map::{Map, Tile};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
+use common::empty_map;
 
 #[test]
 fn test_robot_creation() {
@@ -85,4 +88,22 @@ fn test_robot_type_specialization() {
             RobotType::Explorer => assert_eq!(tile, Tile::Energy),
         }
     }
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_returning_robot_walks_around_obstacles() {
+    let mut map = empty_map(10, 10);
+    map.grid[5][0] = Tile::Station;
+    for y in 2..9 {
+        map.grid[y][2] = Tile::Obstacle;
+    }
+    let home = map.distances_from(0, 5);
+    let mut robot = Robot::build(0, RobotType::Miner, 4, 5);
+    robot.returning = true;
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for _ in 0..home[5 * 10 + 4] {
+        robot.navigate(&map, None, &home, &mut rng, 0, 5);
+    }
+
+    assert_eq!((robot.x, robot.y), (0, 5));
+}
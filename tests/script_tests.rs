@@ -0,0 +1,115 @@
+mod common;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use robot_swarm::map::Tile;
+use robot_swarm::radio::{Network, Radio};
+use robot_swarm::robot::{Command, Robot, RobotType};
+use robot_swarm::script::ScriptController;
+use common::empty_map;
+
+fn robot(id: usize, robot_type: RobotType, x: usize, y: usize) -> Robot {
+    let mut robot = Robot::build(id, robot_type, x, y);
+    robot.energy = 20;
+    robot
+}
+
+fn controller(source: &str, robots: usize) -> ScriptController {
+    ScriptController::new(&[(None, source.to_string())], robots).unwrap()
+}
+
+#[test]
+fn test_scripts_without_decide_are_rejected() {
+    assert!(ScriptController::new(&[(None, "fn decide(robot) { ".to_string())], 1).is_err());
+    assert!(ScriptController::new(&[(None, "fn act(robot) { \"stay\" }".to_string())], 1).is_err());
+}
+
+#[test]
+fn test_commands_move_or_hold_the_robot() {
+    let map = empty_map(10, 10);
+    let network = Network::new(Radio::default(), 2, 1);
+    let mut script = controller(r#"fn decide(robot) { if robot.type == "miner" { "east" } else { "stay" } }"#, 2);
+    let mut robots = vec![robot(0, RobotType::Miner, 4, 4), robot(1, RobotType::Scientist, 6, 6)];
+    let mut rng = StdRng::seed_from_u64(1);
+
+    script.tick(1, &map, &mut robots, &network);
+    assert_eq!(robots[0].command, Some(Command::Move(1, 0)));
+    for robot in robots.iter_mut() {
+        robot.move_robot(&map, &mut rng, 0, 5);
+    }
+
+    assert_eq!((robots[0].x, robots[0].y, robots[0].energy), (5, 4, 19));
+    assert_eq!((robots[1].x, robots[1].y, robots[1].energy), (6, 6, 20));
+    assert_eq!(robots[0].command, None);
+}
+
+#[test]
+fn test_scripts_only_control_their_robot_type() {
+    let map = empty_map(10, 10);
+    let network = Network::new(Radio::default(), 2, 1);
+    let sources = [(Some(RobotType::Explorer), "fn decide(robot) { \"home\" }".to_string())];
+    let mut script = ScriptController::new(&sources, 2).unwrap();
+    let mut robots = vec![robot(0, RobotType::Explorer, 4, 4), robot(1, RobotType::Miner, 6, 6)];
+
+    script.tick(1, &map, &mut robots, &network);
+
+    assert!(script.controls(RobotType::Explorer));
+    assert!(!script.controls(RobotType::Miner));
+    assert_eq!(robots[0].command, Some(Command::Home));
+    assert_eq!(robots[1].command, None);
+}
+
+#[test]
+fn test_messages_reach_the_faction_on_the_next_tick() {
+    let map = empty_map(10, 10);
+    let network = Network::new(Radio::default(), 3, 1);
+    let source = r#"
+        fn decide(robot) {
+            if robot.id == 0 { return #{ action: "stay", say: "hello" }; }
+            if robot.messages.len() > 0 { "north" } else { "stay" }
+        }
+    "#;
+    let mut script = controller(source, 3);
+    let mut robots = vec![
+        robot(0, RobotType::Explorer, 4, 4),
+        robot(1, RobotType::Miner, 6, 6),
+        robot(2, RobotType::Miner, 8, 8),
+    ];
+    robots[2].faction = 1;
+
+    script.tick(1, &map, &mut robots, &network);
+    assert_eq!(robots[1].command, Some(Command::Stay));
+
+    script.tick(2, &map, &mut robots, &network);
+    assert_eq!(robots[1].command, Some(Command::Move(0, -1)));
+    assert_eq!(robots[2].command, Some(Command::Stay));
+}
+
+#[test]
+fn test_runtime_errors_fall_back_to_the_default_behaviour() {
+    let map = empty_map(10, 10);
+    let network = Network::new(Radio::default(), 1, 1);
+    let mut script = controller("fn decide(robot) { \"sideways\" }", 1);
+    let mut robots = vec![robot(0, RobotType::Miner, 4, 4)];
+
+    script.tick(3, &map, &mut robots, &network);
+
+    assert_eq!(robots[0].command, Some(Command::Default));
+    assert_eq!(script.errors, 1);
+    assert!(script.last_error.as_ref().unwrap().contains("sideways"));
+}
+
+#[test]
+fn test_example_miner_script_heads_for_sensed_minerals() {
+    let mut map = empty_map(10, 10);
+    map.grid[4][2] = Tile::Mineral;
+    let network = Network::new(Radio::default(), 1, 1);
+    let source = std::fs::read_to_string("scripts/miner.rhai").unwrap();
+    let mut script = controller(&source, 1);
+    let mut robots = vec![robot(0, RobotType::Miner, 4, 4)];
+
+    script.tick(1, &map, &mut robots, &network);
+
+    assert_eq!(robots[0].command, Some(Command::Move(-1, 0)));
+    assert_eq!(script.errors, 0);
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "server")]
+
+use robot_swarm::map::Tile;
+use robot_swarm::server::{Delta, Server, Snapshot};
+use robot_swarm::simulation::Simulation;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use tungstenite::Message;
+
+fn get(address: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+fn receive(socket: &mut tungstenite::WebSocket<impl Read + Write>) -> Value {
+    match socket.read().unwrap() {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        other => panic!("expected text, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_snapshot_holds_the_whole_state() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+
+    let snapshot = Snapshot::of(&simulation);
+
+    assert_eq!((snapshot.width, snapshot.height), (20, 10));
+    assert_eq!(snapshot.map, simulation.map.grid);
+    assert_eq!(snapshot.robots.len(), 3);
+    assert_eq!(snapshot.stations.len(), 1);
+}
+
+#[test]
+fn test_delta_only_lists_changed_tiles() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let before = simulation.map.grid.clone();
+    let tile = if before[2][3] == Tile::Energy { Tile::Mineral } else { Tile::Energy };
+    simulation.map.grid[2][3] = tile;
+
+    let delta = Delta::between(&before, &simulation, &[]);
+
+    let cells: Vec<(usize, usize, Tile)> = delta.cells.iter().map(|c| (c.x, c.y, c.tile)).collect();
+    assert_eq!(cells, [(3, 2, tile)]);
+    assert_eq!(delta.robots.len(), 3);
+}
+
+#[test]
+fn test_state_endpoint_serves_the_latest_snapshot() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let mut server = Server::start("127.0.0.1:0", &simulation).unwrap();
+    simulation.step();
+    server.publish(&simulation, &[]);
+
+    let response = get(server.address, "/state");
+
+    let body = response.split("\r\n\r\n").nth(1).unwrap();
+    let state: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(state["tick"], 1);
+    assert_eq!(state["robots"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_unknown_paths_are_not_found() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let server = Server::start("127.0.0.1:0", &simulation).unwrap();
+
+    assert!(get(server.address, "/nope").starts_with("HTTP/1.1 404"));
+    assert!(get(server.address, "/").contains("<html>"));
+}
+
+#[test]
+fn test_websocket_streams_a_snapshot_then_deltas() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let mut server = Server::start("127.0.0.1:0", &simulation).unwrap();
+    let (mut socket, _) = tungstenite::connect(format!("ws://{}/ws", server.address)).unwrap();
+
+    let snapshot = receive(&mut socket);
+    simulation.step();
+    let events = simulation.take_events();
+    server.publish(&simulation, &events);
+    let delta = receive(&mut socket);
+
+    assert_eq!(snapshot["type"], "snapshot");
+    assert_eq!(snapshot["tick"], 0);
+    assert_eq!(delta["type"], "delta");
+    assert_eq!(delta["tick"], 1);
+    assert_eq!(delta["events"].as_array().unwrap().len(), events.len());
+    assert_eq!(server.watchers(), 1);
+}
+
+#[test]
+fn test_watchers_are_answered_and_dropped_once_closed() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let mut server = Server::start("127.0.0.1:0", &simulation).unwrap();
+    let (mut socket, _) = tungstenite::connect(format!("ws://{}/ws", server.address)).unwrap();
+    receive(&mut socket);
+
+    socket.send(Message::Ping(vec![1, 2])).unwrap();
+    let pong = socket.read().unwrap();
+    socket.close(None).unwrap();
+    while socket.read().is_ok() {}
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while server.watchers() > 0 && Instant::now() < deadline {
+        server.publish(&simulation, &[]);
+        sleep(Duration::from_millis(20));
+    }
+
+    assert_eq!(pong, Message::Pong(vec![1, 2]));
+    assert_eq!(server.watchers(), 0);
+}
+
+#[test]
+fn test_a_watcher_that_does_not_read_never_stalls_the_run() {
+    // Big enough that the snapshot does not fit in the socket buffers
+    let simulation = Simulation::new(42, 1000, 1000, 3);
+    let mut server = Server::start("127.0.0.1:0", &simulation).unwrap();
+    let (_socket, _) = tungstenite::connect(format!("ws://{}/ws", server.address)).unwrap();
+    sleep(Duration::from_millis(200));
+
+    let started = Instant::now();
+    server.publish(&simulation, &[]);
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(server.watchers(), 1);
+}
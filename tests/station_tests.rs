@@ -1,6 +1,8 @@
-use robot_swarm::station::Station;
+use robot_swarm::station::{Expansion, Inventory, Station};
 use robot_swarm::robot::Robot;
+use robot_swarm::event::Event;
 use robot_swarm::map::Tile;
+use robot_swarm::simulation::{Simulation, SimulationConfig};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
@@ -85,4 +87,67 @@ fn test_station_display_format() {
     assert!(output.contains("Energy"));
     assert!(output.contains("Mineral"));
     assert!(output.contains("Scientific"));
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_preplaced_stations_are_built() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        stations: vec![(19, 9), (50, 50)],
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    assert_eq!(simulation.stations.len(), 2);
+    assert_eq!(simulation.map.grid[9][19], Tile::Station);
+    assert!(simulation
+        .take_events()
+        .iter()
+        .any(|r| r.event == Event::StationBuilt { station: 1, x: 19, y: 9, faction: 0 }));
+}
+
+#[test]
+fn test_robots_head_for_nearest_station() {
+    let simulation = Simulation::from_config(SimulationConfig {
+        stations: vec![(19, 5)],
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    assert_eq!(simulation.nearest_station(0, 0, 5), 0);
+    assert_eq!(simulation.nearest_station(0, 19, 5), 1);
+}
+
+#[test]
+fn test_outpost_built_from_shared_minerals() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        stations: vec![(19, 5)],
+        expansion: Some(Expansion { cost: 2, inventory: Inventory::Shared, spacing: 1 }),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+    simulation.map.grid[1][9] = Tile::Empty;
+    simulation.stations[0].deliver((9, 1, Tile::Mineral));
+    simulation.stations[1].deliver((18, 5, Tile::Mineral));
+    simulation.map.grid[5][18] = Tile::Empty;
+
+    simulation.step();
+
+    assert_eq!(simulation.stations.len(), 3);
+    assert_eq!((simulation.stations[2].x, simulation.stations[2].y), (9, 1));
+    assert_eq!(simulation.stations.iter().map(|s| s.spent_minerals).sum::<usize>(), 2);
+}
+
+#[test]
+fn test_separate_inventory_needs_one_rich_station() {
+    let mut simulation = Simulation::from_config(SimulationConfig {
+        stations: vec![(19, 5)],
+        expansion: Some(Expansion { cost: 2, inventory: Inventory::Separate, spacing: 1 }),
+        ..SimulationConfig::new(42, 20, 10)
+    });
+    simulation.map.grid[1][9] = Tile::Empty;
+    simulation.stations[0].deliver((9, 1, Tile::Mineral));
+    simulation.stations[1].deliver((18, 5, Tile::Mineral));
+    simulation.map.grid[5][18] = Tile::Empty;
+
+    simulation.step();
+
+    assert_eq!(simulation.stations.len(), 2);
+    assert_eq!(simulation.stations[0].available_minerals(), 1);
+}
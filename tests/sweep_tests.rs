@@ -0,0 +1,91 @@
+use robot_swarm::robot::RobotType;
+use robot_swarm::simulation::{Simulation, SimulationConfig, Swarm};
+use robot_swarm::sweep::{aggregate, mean_stddev, results_csv, run_point, run_sweep, RunOutcome, SweepGrid};
+
+#[test]
+fn test_sweep_grid_points() {
+    let grid = SweepGrid {
+        explorers: vec![1, 2],
+        miners: vec![0, 1, 2],
+        scientists: vec![1],
+        sizes: vec![(20, 10), (30, 15)],
+        densities: vec![0.03],
+        seeds: vec![0, 1],
+        max_ticks: 100,
+    };
+
+    assert_eq!(grid.points().len(), 2 * 3 * 2);
+}
+
+#[test]
+fn test_fixed_swarm_composition() {
+    let simulation = Simulation::from_config(SimulationConfig {
+        swarm: Swarm::Fixed { explorers: 2, miners: 1, scientists: 3 },
+        ..SimulationConfig::new(42, 20, 10)
+    });
+
+    let count = |t| simulation.robots.iter().filter(|r| r.robot_type == t).count();
+    assert_eq!(count(RobotType::Explorer), 2);
+    assert_eq!(count(RobotType::Miner), 1);
+    assert_eq!(count(RobotType::Scientist), 3);
+}
+
+#[test]
+fn test_mean_stddev() {
+    let (mean, stddev) = mean_stddev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    assert_eq!(mean, 5.0);
+    assert_eq!(stddev, 2.0);
+
+    assert_eq!(mean_stddev(&[]), (0.0, 0.0));
+}
+
+#[test]
+fn test_run_point_respects_tick_limit() {
+    let grid = SweepGrid::default();
+    let outcome = run_point(grid.points()[0], 3, 5);
+
+    assert!(outcome.ticks <= 5);
+}
+
+#[test]
+fn test_run_sweep_aggregates_every_seed() {
+    let grid = SweepGrid {
+        explorers: vec![1, 2],
+        seeds: (0..4).collect(),
+        max_ticks: 200,
+        ..SweepGrid::default()
+    };
+
+    let results = run_sweep(&grid, 2);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.runs == 4));
+
+    let csv = results_csv(&results);
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.starts_with("explorers,miners,scientists"));
+}
+
+#[test]
+fn test_run_point_handles_short_maps() {
+    let grid = SweepGrid {
+        sizes: vec![(10, 4)],
+        ..SweepGrid::default()
+    };
+
+    let outcome = run_point(grid.points()[0], 1, 50);
+
+    assert!(outcome.ticks <= 50);
+}
+
+#[test]
+fn test_ticks_average_only_completed_runs() {
+    let point = SweepGrid::default().points()[0];
+    let run = |ticks, completed| RunOutcome { delivered: 1, ticks, completed };
+
+    let mixed = aggregate(point, &[run(20, true), run(40, true), run(100, false)]);
+    let capped = aggregate(point, &[run(100, false), run(100, false)]);
+
+    assert_eq!((mixed.completed, mixed.ticks_mean, mixed.ticks_stddev), (2, Some(30.0), Some(10.0)));
+    assert_eq!((capped.completed, capped.ticks_mean), (0, None));
+    assert!(results_csv(&[capped]).lines().nth(1).unwrap().ends_with(",1.000,0.000,,"));
+}
@@ -0,0 +1,90 @@
+use crossterm::style::Color;
+use robot_swarm::simulation::Simulation;
+use robot_swarm::tui::{compose, Cell, Frame, Glyphs, Status, PANEL_WIDTH};
+use robot_swarm::viewport::Viewport;
+use std::time::Duration;
+
+fn row_text(frame: &Frame, y: usize) -> String {
+    (0..frame.width)
+        .map(|x| frame.get(x, y).symbol)
+        .filter(|&c| c != '\0')
+        .collect()
+}
+
+#[test]
+fn test_frame_starts_blank() {
+    let frame = Frame::new(4, 2);
+
+    for y in 0..2 {
+        for x in 0..4 {
+            assert_eq!(frame.get(x, y), Cell::BLANK);
+        }
+    }
+}
+
+#[test]
+fn test_frame_wide_glyph_reserves_two_columns() {
+    let mut frame = Frame::new(6, 1);
+    frame.text(0, 0, "⚡ab", Color::Yellow);
+
+    assert_eq!(frame.get(0, 0).symbol, '⚡');
+    assert_eq!(frame.get(1, 0), Cell::CONTINUATION);
+    assert_eq!(frame.get(2, 0).symbol, 'a');
+    assert_eq!(frame.get(3, 0).symbol, 'b');
+}
+
+#[test]
+fn test_frame_clips_out_of_bounds() {
+    let mut frame = Frame::new(3, 1);
+    frame.text(1, 0, "hello", Color::White);
+    frame.put(0, 5, 'x', Color::White);
+
+    assert_eq!(row_text(&frame, 0), " he");
+}
+
+#[test]
+fn test_compose_shows_panel() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    simulation.step();
+
+    let status = Status { paused: true, ..Status::new(Duration::from_millis(400)) };
+    let frame = compose(&simulation, &status, &mut Viewport::default(), (120, 30), Glyphs::Emoji);
+
+    assert_eq!((frame.width, frame.height), (120, 30));
+    assert!(row_text(&frame, 0).contains("Tick 1"));
+    assert!(row_text(&frame, 0).contains("paused"));
+    assert!(row_text(&frame, 2).contains("Station"));
+    for (i, robot) in simulation.robots.iter().enumerate() {
+        assert!(row_text(&frame, 9 + i).contains(&format!("{:?}", robot.robot_type)));
+    }
+}
+
+#[test]
+fn test_compose_fits_large_map_in_terminal() {
+    let simulation = Simulation::new(42, 500, 500, 3);
+    let status = Status::new(Duration::from_millis(400));
+    let mut viewport = Viewport::default();
+
+    let frame = compose(&simulation, &status, &mut viewport, (100, 40), Glyphs::Emoji);
+
+    assert_eq!((frame.width, frame.height), (100, 40));
+    assert_eq!(viewport.width * 2 + PANEL_WIDTH + 2, 100);
+    assert_eq!(viewport.height, 39);
+    assert!((0..frame.height).any(|y| row_text(&frame, y).contains("Robots")));
+}
+
+#[test]
+fn test_fast_mode_draws_every_nth_tick() {
+    let mut status = Status { render_every: 5, ..Status::new(Duration::ZERO) };
+
+    let drawn: Vec<u64> = (0..12).filter(|&tick| status.should_draw(tick)).collect();
+    assert_eq!(drawn, vec![0, 5, 10]);
+
+    status.paused = true;
+    assert!(status.should_draw(3));
+
+    status.paused = false;
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let frame = compose(&simulation, &status, &mut Viewport::default(), (120, 30), Glyphs::Ascii);
+    assert!(row_text(&frame, 0).contains("drawing every 5"));
+}
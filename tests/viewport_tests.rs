@@ -0,0 +1,78 @@
+mod common;
+
+use robot_swarm::map::Tile;
+use robot_swarm::robot::{Robot, RobotType};
+use robot_swarm::viewport::{aggregate, Block, Viewport};
+use common::empty_map;
+
+#[test]
+fn test_viewport_fits_small_map() {
+    let map = empty_map(20, 10);
+    let mut viewport = Viewport::default();
+
+    viewport.fit(200, 50, &map, &[]);
+
+    assert_eq!((viewport.width, viewport.height), (20, 10));
+    assert_eq!((viewport.x, viewport.y), (0, 0));
+}
+
+#[test]
+fn test_viewport_scroll_is_clamped() {
+    let map = empty_map(100, 100);
+    let mut viewport = Viewport::default();
+    viewport.fit(40, 10, &map, &[]);
+
+    viewport.scroll(-5, -5, &map);
+    assert_eq!((viewport.x, viewport.y), (0, 0));
+
+    viewport.scroll(1000, 1000, &map);
+    assert_eq!((viewport.x, viewport.y), (80, 90));
+}
+
+#[test]
+fn test_viewport_follows_robot() {
+    let map = empty_map(100, 100);
+    let robots = vec![Robot::build(0, RobotType::Explorer, 50, 60)];
+    let mut viewport = Viewport::default();
+
+    viewport.cycle_follow(&robots);
+    viewport.fit(40, 10, &map, &robots);
+
+    assert_eq!(viewport.follow, Some(0));
+    assert!(viewport.contains(50, 60));
+
+    viewport.scroll(1, 0, &map);
+    assert_eq!(viewport.follow, None);
+}
+
+#[test]
+fn test_viewport_zoom_covers_more_cells() {
+    let map = empty_map(100, 100);
+    let mut viewport = Viewport::default();
+
+    viewport.zoom_out();
+    viewport.zoom_out();
+    viewport.fit(40, 10, &map, &[]);
+
+    assert_eq!(viewport.zoom, 4);
+    assert_eq!(viewport.span(), (80, 40));
+}
+
+#[test]
+fn test_aggregate_priorities() {
+    let mut map = empty_map(10, 10);
+    map.grid[1][1] = Tile::Energy;
+    map.grid[2][2] = Tile::Scientific;
+
+    assert_eq!(aggregate(&map, &[], 0, 0, 4, 4), Block::Tile(Tile::Scientific));
+
+    let robots = vec![Robot::build(0, RobotType::Miner, 3, 3)];
+    assert_eq!(aggregate(&map, &robots, 0, 0, 4, 4), Block::Robot(RobotType::Miner));
+
+    for row in map.grid[5..].iter_mut() {
+        for tile in row[5..].iter_mut() {
+            *tile = Tile::Obstacle;
+        }
+    }
+    assert_eq!(aggregate(&map, &[], 5, 5, 5, 5), Block::Tile(Tile::Obstacle));
+}
@@ -0,0 +1,77 @@
+use robot_swarm::event::{Event, Record};
+use robot_swarm::simulation::Simulation;
+use robot_swarm::watchdog::{Limits, StopReason, StuckDetector};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_tick_limit_stops_run() {
+    let mut simulation = Simulation::new(42, 20, 10, 3);
+    let limits = Limits { max_ticks: Some(3), timeout: None };
+    let started = Instant::now();
+
+    let mut reason = None;
+    while reason.is_none() {
+        simulation.step();
+        reason = limits.check(&simulation, started);
+    }
+
+    assert_eq!(simulation.tick, 3);
+    assert_eq!(reason, Some(StopReason::TickLimit(3)));
+}
+
+#[test]
+fn test_timeout_stops_run() {
+    let simulation = Simulation::new(42, 20, 10, 3);
+    let limits = Limits { max_ticks: None, timeout: Some(Duration::ZERO) };
+
+    assert_eq!(limits.check(&simulation, Instant::now()), Some(StopReason::Timeout(Duration::ZERO)));
+    assert_eq!(Limits::default().check(&simulation, Instant::now()), None);
+}
+
+#[test]
+fn test_oscillating_robot_is_reported_stuck() {
+    let mut simulation = Simulation::new(42, 20, 10, 1);
+    let mut detector = StuckDetector::new(&simulation, 4);
+
+    let mut reported = Vec::new();
+    for tick in 1..=6 {
+        simulation.tick = tick;
+        let (from, to) = if tick % 2 == 1 { ((0, 5), (1, 5)) } else { ((1, 5), (0, 5)) };
+        let moved = Record { tick, event: Event::RobotMoved { robot: 0, from, to } };
+        reported.extend(detector.record(&simulation, &[moved]));
+    }
+
+    // Only the first step onto (1, 5) counts as progress
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0].tick, 5);
+    assert!(matches!(reported[0].event, Event::RobotStuck { robot: 0, since: 1, .. }));
+    assert!(detector.all_stuck());
+}
+
+#[test]
+fn test_progress_clears_stuck_state() {
+    let mut simulation = Simulation::new(42, 20, 10, 1);
+    let mut detector = StuckDetector::new(&simulation, 2);
+
+    simulation.tick = 2;
+    detector.record(&simulation, &[]);
+    assert!(detector.is_stuck(0));
+
+    simulation.tick = 3;
+    let delivered = Record {
+        tick: 3,
+        event: Event::DiscoveryDelivered { robot: 0, x: 4, y: 4, tile: robot_swarm::map::Tile::Mineral },
+    };
+    detector.record(&simulation, &[delivered]);
+    assert!(!detector.is_stuck(0));
+    assert!(detector.stuck_robots().is_empty());
+}
+
+#[test]
+fn test_stuck_event_round_trips_through_log() {
+    let record = Record { tick: 9, event: Event::RobotStuck { robot: 2, x: 3, y: 4, since: 1 } };
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.contains("\"event\":\"RobotStuck\""));
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use robot_swarm::event::Event;
+use robot_swarm::map::{Map, Tile};
+use robot_swarm::robot::{Command, Robot, RobotType};
+use robot_swarm::station::Station;
+use robot_swarm::simulation::{Simulation, SimulationConfig};
+use robot_swarm::world::{Dynamics, Storm, World};
+use common::empty_map;
+
+#[test]
+fn test_static_world_by_default() {
+    let mut map = Map::new(42, 20, 10);
+    let before = map.grid.clone();
+    let mut world = World::new(Dynamics::default(), &map, 42);
+
+    for _ in 0..50 {
+        assert!(world.tick(&mut map, &mut []).is_empty());
+    }
+    assert_eq!(map.grid, before);
+    assert!(world.storms.is_empty());
+}
+
+#[test]
+fn test_energy_regrows_where_it_was_harvested() {
+    let mut map = empty_map(10, 10);
+    map.grid[3][4] = Tile::Energy;
+    let mut world = World::new(Dynamics { energy_regrowth: 1.0, ..Dynamics::default() }, &map, 42);
+
+    map.grid[3][4] = Tile::Empty;
+    let events = world.tick(&mut map, &mut []);
+
+    assert_eq!(map.grid[3][4], Tile::Energy);
+    assert_eq!(events, vec![Event::ResourceSpawned { x: 4, y: 3, tile: Tile::Energy }]);
+}
+
+#[test]
+fn test_mineral_veins_appear() {
+    let mut map = empty_map(10, 10);
+    let mut world = World::new(Dynamics { mineral_veins: 1.0, ..Dynamics::default() }, &map, 7);
+
+    let events = world.tick(&mut map, &mut []);
+
+    let minerals = map.grid.iter().flatten().filter(|&&t| t == Tile::Mineral).count();
+    assert!(minerals >= 1);
+    assert_eq!(events.len(), minerals);
+}
+
+#[test]
+fn test_storms_drain_robots_caught_inside() {
+    let mut map = empty_map(10, 10);
+    let mut world = World::new(Dynamics { storms: 1, storm_damage: 3, ..Dynamics::default() }, &map, 42);
+    let mut robots = vec![Robot::build(0, RobotType::Miner, 0, 0)];
+
+    // Cover the whole map so wherever the storm drifts the robot is inside
+    world.storms[0] = Storm { x: 5, y: 5, dx: 1, dy: 0, radius: 20 };
+    let events = world.tick(&mut map, &mut robots);
+
+    assert_eq!(robots[0].energy, 7);
+    assert_eq!(events, vec![Event::RobotDamaged { robot: 0, x: 0, y: 0, amount: 3 }]);
+    assert!(world.storm_at(9, 9));
+}
+
+#[test]
+fn test_dynamic_runs_are_reproducible() {
+    let run = || {
+        let mut simulation = Simulation::from_config(SimulationConfig {
+            dynamics: Dynamics::preset(),
+            ..SimulationConfig::new(42, 30, 15)
+        });
+        let mut events = Vec::new();
+        for _ in 0..200 {
+            simulation.step();
+            events.extend(simulation.take_events());
+        }
+        events
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_storms_block_movement_and_paths() {
+    let mut map = empty_map(10, 10);
+    let mut world = World::new(Dynamics { storms: 1, storm_damage: 0, ..Dynamics::default() }, &map, 42);
+    world.storms[0] = Storm { x: 5, y: 5, dx: 0, dy: 0, radius: 1 };
+    world.tick(&mut map, &mut []);
+    let (sx, sy) = (world.storms[0].x, world.storms[0].y);
+
+    assert!(!map.is_passable(sx, sy));
+    assert_eq!(map.distances_from(0, 0)[sy * 10 + sx], usize::MAX);
+
+    // A robot right next to the storm cannot step into it
+    let mut robot = Robot::build(0, RobotType::Miner, sx - 2, sy);
+    robot.energy = 20;
+    robot.command = Some(Command::Move(1, 0));
+    robot.navigate(&map, None, &map.distances_from(0, 5), &mut rand::SeedableRng::seed_from_u64(1), 0, 5);
+    assert_eq!((robot.x, robot.y), (sx - 2, sy));
+}
+
+#[test]
+fn test_regrown_energy_is_delivered_every_time() {
+    let mut map = empty_map(10, 10);
+    map.grid[3][4] = Tile::Energy;
+    let mut world = World::new(Dynamics { energy_regrowth: 1.0, ..Dynamics::default() }, &map, 42);
+    let mut station = Station::new(0, 5);
+    let mut robot = Robot::build(0, RobotType::Miner, 4, 3);
+
+    for _ in 0..2 {
+        robot.perform_action(&mut map);
+        station.collect_discoveries(&mut robot);
+        world.tick(&mut map, &mut []);
+    }
+
+    assert_eq!(station.count(Tile::Energy), 2);
+    assert_eq!(station.deliveries(), 2);
+    assert_eq!(station.discoveries.len(), 1);
+}